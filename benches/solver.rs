@@ -0,0 +1,101 @@
+//! Benchmarks over the sample corpus, generated boards and the placement calculators.
+//!
+//! The larger table samples take tens of seconds per run; pick benchmarks with a filter,
+//! e.g. `cargo bench -- corpus/data1`.
+
+use std::hint::black_box;
+use std::path::Path;
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use nonogram_solver::{
+    board::Vec2,
+    display::NullDisplay,
+    generator::{derive_hints, generate_board, Random},
+    solver::{
+        calculator::{CombCounter, NumberDistributionCalculator},
+        parser::{AutoSolverParser, SolverParseResult, SolverParser},
+        Solver,
+    },
+};
+
+fn solve(puzzle: &SolverParseResult) {
+    let mut solver = Solver::new(
+        puzzle.board_size,
+        puzzle.row_hints.clone(),
+        puzzle.column_hints.clone(),
+        Box::new(NullDisplay),
+    )
+    .unwrap();
+    let _ = black_box(solver.solve());
+}
+
+fn load(path: &Path) -> SolverParseResult {
+    let content = std::fs::read_to_string(path).unwrap();
+    AutoSolverParser::new(&content).parse().unwrap()
+}
+
+fn corpus(c: &mut Criterion) {
+    let mut paths = (1..=5)
+        .map(|i| format!("sample/data{}.txt", i).into())
+        .collect::<Vec<std::path::PathBuf>>();
+    let mut tables = std::fs::read_dir("sample/table")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect::<Vec<_>>();
+    tables.sort();
+    paths.extend(tables);
+
+    let mut group = c.benchmark_group("corpus");
+    group.sample_size(10);
+    for path in paths {
+        let puzzle = load(&path);
+        let name = path
+            .strip_prefix("sample")
+            .unwrap_or(&path)
+            .with_extension("")
+            .display()
+            .to_string();
+        group.bench_function(name, |b| b.iter(|| solve(&puzzle)));
+    }
+    group.finish();
+}
+
+fn generated(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generated");
+    group.sample_size(10);
+    for size in [50, 100] {
+        // 빈칸이 적어야 줄마다 배치 수가 감당할 만하다
+        let board = generate_board(Vec2::new(size, size), 0.85, &mut Random::new(size as u64));
+        let puzzle = derive_hints(&board);
+        group.bench_function(format!("{}x{}", size, size), |b| b.iter(|| solve(&puzzle)));
+    }
+    group.finish();
+}
+
+fn calculators(c: &mut Criterion) {
+    c.bench_function("calc_comb_count/cold", |b| {
+        b.iter_batched(
+            CombCounter::new,
+            |mut counter| counter.calc_comb_count(black_box(40), black_box(8)),
+            BatchSize::SmallInput,
+        )
+    });
+
+    let mut counter = CombCounter::new();
+    c.bench_function("calc_comb_count/cached", |b| {
+        b.iter(|| counter.calc_comb_count(black_box(40), black_box(8)))
+    });
+
+    let mut calculator = NumberDistributionCalculator::new();
+    let count = calculator.comb_counter.calc_comb_count(12, 6);
+    c.bench_function("calc_distribute_number/12_into_6", |b| {
+        b.iter(|| {
+            for index in 0..count {
+                black_box(calculator.calc_distribute_number(12, 6, index).unwrap());
+            }
+        })
+    });
+}
+
+criterion_group!(benches, calculators, generated, corpus);
+criterion_main!(benches);
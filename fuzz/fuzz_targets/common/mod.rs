@@ -0,0 +1,21 @@
+use nonogram_solver::board::Vec2;
+use nonogram_solver::display::NullDisplay;
+use nonogram_solver::solver::limits::SolveLimits;
+use nonogram_solver::solver::Solver;
+
+/// Creates and runs a solver, bounded so that a single input cannot hang the fuzzer.
+///
+/// Sizes are not capped: the hint count has to match the size before anything the size of the
+/// board is allocated, and the budget stops lines with too many placements.
+pub fn solve(size: Vec2, row_hints: Vec<Vec<usize>>, column_hints: Vec<Vec<usize>>) {
+    let Ok(mut solver) = Solver::new(size, row_hints, column_hints, Box::new(NullDisplay)) else {
+        return;
+    };
+    solver.set_limits(SolveLimits {
+        max_placements: Some(100_000),
+        // 큰 보드는 줄마다 보드 전체를 복사하므로 횟수도 제한한다
+        max_line_passes: Some(1_000),
+        ..Default::default()
+    });
+    let _ = solver.solve();
+}
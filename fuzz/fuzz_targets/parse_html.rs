@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nonogram_solver::solver::parser::{HtmlTableSolverParser, SolverParser};
+
+mod common;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        if let Ok(result) = HtmlTableSolverParser::new(content).parse() {
+            common::solve(result.board_size, result.row_hints, result.column_hints);
+        }
+    }
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nonogram_solver::board::Vec2;
+
+mod common;
+
+fuzz_target!(|input: (u16, u16, Vec<Vec<usize>>, Vec<Vec<usize>>)| {
+    let (row, column, row_hints, column_hints) = input;
+    common::solve(
+        Vec2::new(row as usize, column as usize),
+        row_hints,
+        column_hints,
+    );
+});
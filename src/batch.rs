@@ -0,0 +1,436 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde_json::json;
+
+use crate::board::Vec2;
+use crate::display::NullDisplay;
+use crate::solver::error::SolverError;
+use crate::solver::limits::SolveLimits;
+use crate::solver::parser::{AutoSolverParser, SolverParser};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum BatchStatus {
+    Solved,
+    /// Line logic stopped with unknown cells left.
+    Stalled,
+    Timeout,
+    Contradiction,
+    /// The file could not be read or is not a valid puzzle.
+    Error,
+}
+
+impl BatchStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BatchStatus::Solved => "solved",
+            BatchStatus::Stalled => "stalled",
+            BatchStatus::Timeout => "timeout",
+            BatchStatus::Contradiction => "contradiction",
+            BatchStatus::Error => "error",
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        [
+            BatchStatus::Solved,
+            BatchStatus::Stalled,
+            BatchStatus::Timeout,
+            BatchStatus::Contradiction,
+            BatchStatus::Error,
+        ]
+        .into_iter()
+        .find(|status| status.as_str() == text.trim())
+    }
+
+    /// Lower is better. A result regresses when its rank gets higher.
+    fn rank(&self) -> usize {
+        *self as usize
+    }
+}
+
+impl Display for BatchStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+pub struct BatchEntry {
+    pub file: String,
+    pub size: Option<Vec2>,
+    pub status: BatchStatus,
+    pub elapsed: Duration,
+    pub line_passes: usize,
+    pub message: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BatchFormat {
+    Table,
+    Csv,
+    Json,
+}
+
+/// Lists the files to solve: every file under a directory, or the files matching a glob.
+pub fn collect_inputs(pattern: &str) -> Result<Vec<PathBuf>, String> {
+    let path = Path::new(pattern);
+    let mut files = if path.is_dir() {
+        let mut files = Vec::new();
+        collect_dir(path, &mut files).map_err(|e| format!("Failed to read directory: {}", e))?;
+        files
+    } else {
+        glob::glob(pattern)
+            .map_err(|e| format!("Invalid pattern: {}", e))?
+            .filter_map(Result::ok)
+            .filter(|path| path.is_file())
+            .collect()
+    };
+    files.sort();
+    Ok(files)
+}
+
+fn collect_dir(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_dir(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+pub fn solve_file(path: &Path, timeout: Option<Duration>) -> BatchEntry {
+    let start = Instant::now();
+    let file = path.display().to_string();
+    let error = |message: String| BatchEntry {
+        file: file.clone(),
+        size: None,
+        status: BatchStatus::Error,
+        elapsed: start.elapsed(),
+        line_passes: 0,
+        message: Some(message),
+    };
+
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => return error(format!("Failed to read file: {}", e)),
+    };
+    let mut solver = match AutoSolverParser::new(&content).create_solver(Box::new(NullDisplay)) {
+        Ok(solver) => solver,
+        Err(e) => return error(e),
+    };
+    solver.set_limits(SolveLimits {
+        deadline: timeout.map(|timeout| start + timeout),
+        ..Default::default()
+    });
+
+    let (status, message) = match solver.solve() {
+        Ok(()) if solver.is_solved() => (BatchStatus::Solved, None),
+        Ok(()) => (BatchStatus::Stalled, None),
+        Err(SolverError::Aborted(e)) => (BatchStatus::Timeout, Some(e.reason.to_string())),
+        Err(e @ SolverError::Contradiction(_)) => (BatchStatus::Contradiction, Some(e.to_string())),
+        Err(e) => (BatchStatus::Error, Some(e.to_string())),
+    };
+
+    BatchEntry {
+        file,
+        size: Some(solver.board().size()),
+        status,
+        elapsed: start.elapsed(),
+        line_passes: solver.line_passes(),
+        message,
+    }
+}
+
+/// Solves every file using `jobs` worker threads. Entries keep the order of `paths`.
+pub fn run_batch(paths: &[PathBuf], timeout: Option<Duration>, jobs: usize) -> Vec<BatchEntry> {
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(paths.len()));
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, paths.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(path) = paths.get(index) else {
+                    break;
+                };
+                let entry = solve_file(path, timeout);
+                results.lock().unwrap().push((index, entry));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, entry)| entry).collect()
+}
+
+fn format_size(size: Option<Vec2>) -> String {
+    size.map_or_else(
+        || "-".to_string(),
+        |size| format!("{}x{}", size.row, size.column),
+    )
+}
+
+pub fn format_entries(entries: &[BatchEntry], format: BatchFormat) -> String {
+    match format {
+        BatchFormat::Table => format_table(entries),
+        BatchFormat::Csv => format_csv(entries),
+        BatchFormat::Json => format_json(entries),
+    }
+}
+
+fn format_table(entries: &[BatchEntry]) -> String {
+    let header = ["file", "size", "status", "time (ms)", "lines"];
+    let rows = entries
+        .iter()
+        .map(|entry| {
+            [
+                entry.file.clone(),
+                format_size(entry.size),
+                entry.status.to_string(),
+                entry.elapsed.as_millis().to_string(),
+                entry.line_passes.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let widths = (0..header.len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .chain(std::iter::once(header[column].len()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+
+    let mut text = String::new();
+    let mut push_row = |cells: &[String]| {
+        let line = cells
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        text.push_str(line.trim_end());
+        text.push('\n');
+    };
+    push_row(&header.map(String::from));
+    push_row(
+        &widths
+            .iter()
+            .map(|&width| "-".repeat(width))
+            .collect::<Vec<_>>(),
+    );
+    for row in &rows {
+        push_row(row);
+    }
+
+    let solved = entries
+        .iter()
+        .filter(|entry| entry.status == BatchStatus::Solved)
+        .count();
+    text.push_str(&format!("\n{}/{} solved\n", solved, entries.len()));
+    text
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn format_csv(entries: &[BatchEntry]) -> String {
+    let mut text = String::from("file,size,status,time_ms,lines,message\n");
+    for entry in entries {
+        text.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            csv_field(&entry.file),
+            format_size(entry.size),
+            entry.status,
+            entry.elapsed.as_millis(),
+            entry.line_passes,
+            csv_field(entry.message.as_deref().unwrap_or_default()),
+        ));
+    }
+    text
+}
+
+fn format_json(entries: &[BatchEntry]) -> String {
+    let entries = entries
+        .iter()
+        .map(|entry| {
+            json!({
+                "file": entry.file,
+                "rows": entry.size.map(|size| size.row),
+                "columns": entry.size.map(|size| size.column),
+                "status": entry.status.as_str(),
+                "time_ms": entry.elapsed.as_millis() as u64,
+                "lines": entry.line_passes,
+                "message": entry.message,
+            })
+        })
+        .collect::<Vec<_>>();
+    serde_json::to_string_pretty(&entries).unwrap_or_default()
+}
+
+/// Splits CSV text into records, undoing the quoting of [`csv_field`].
+fn parse_csv(content: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => record.push(std::mem::take(&mut field)),
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            '\r' if !quoted => {}
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records.retain(|record| record.iter().any(|field| !field.trim().is_empty()));
+    records
+}
+
+/// Reads expected statuses from a CSV file with `file` and `status` columns,
+/// such as the output of `--report-format csv`.
+pub fn parse_expected(content: &str) -> Result<HashMap<String, BatchStatus>, String> {
+    let mut records = parse_csv(content).into_iter();
+    let header = records
+        .next()
+        .ok_or_else(|| "Expected results file is empty.".to_string())?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|field| field.trim() == name)
+            .ok_or_else(|| format!("Expected results file has no \"{}\" column.", name))
+    };
+    let file_column = column("file")?;
+    let status_column = column("status")?;
+
+    records
+        .map(|record| {
+            let file = record
+                .get(file_column)
+                .ok_or_else(|| format!("Missing file in line: {}", record.join(",")))?;
+            let status = record
+                .get(status_column)
+                .and_then(|status| BatchStatus::parse(status))
+                .ok_or_else(|| format!("Invalid status in line: {}", record.join(",")))?;
+            Ok((file.trim().to_string(), status))
+        })
+        .collect()
+}
+
+/// Entries whose status is worse than expected, with the expected status.
+pub fn find_regressions<'a>(
+    entries: &'a [BatchEntry],
+    expected: &HashMap<String, BatchStatus>,
+) -> Vec<(&'a BatchEntry, BatchStatus)> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let expected = *expected.get(&entry.file)?;
+            (entry.status.rank() > expected.rank()).then_some((entry, expected))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_batch() {
+        let paths = collect_inputs("./sample/data[12].txt").unwrap();
+        assert_eq!(paths.len(), 2);
+
+        let entries = run_batch(&paths, None, 2);
+        assert_eq!(entries[0].file, paths[0].display().to_string());
+        assert!(entries
+            .iter()
+            .all(|entry| entry.status == BatchStatus::Solved));
+        assert!(entries.iter().all(|entry| entry.line_passes > 0));
+    }
+
+    #[test]
+    fn test_collect_dir() {
+        let paths = collect_inputs("./sample/table").unwrap();
+        assert_eq!(paths.len(), 7);
+    }
+
+    #[test]
+    fn test_regressions_from_csv() {
+        let entry = |file: &str, status| BatchEntry {
+            file: file.to_string(),
+            size: None,
+            status,
+            elapsed: Duration::ZERO,
+            line_passes: 0,
+            message: None,
+        };
+        let expected = parse_expected(&format_csv(&[
+            entry("a.txt", BatchStatus::Solved),
+            entry("b.txt", BatchStatus::Stalled),
+            entry("c.txt", BatchStatus::Solved),
+        ]))
+        .unwrap();
+
+        let entries = [
+            entry("a.txt", BatchStatus::Timeout),
+            entry("b.txt", BatchStatus::Solved),
+            entry("c.txt", BatchStatus::Solved),
+        ];
+        let regressions = find_regressions(&entries, &expected);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].0.file, "a.txt");
+        assert_eq!(regressions[0].1, BatchStatus::Solved);
+    }
+
+    #[test]
+    fn test_expected_from_quoted_csv() {
+        let entry = |file: &str, status, message: Option<&str>| BatchEntry {
+            file: file.to_string(),
+            size: Some(Vec2::new(5, 5)),
+            status,
+            elapsed: Duration::ZERO,
+            line_passes: 0,
+            message: message.map(str::to_string),
+        };
+        let expected = parse_expected(&format_csv(&[
+            entry("a,b.txt", BatchStatus::Solved, None),
+            entry(
+                "\"c\".txt",
+                BatchStatus::Stalled,
+                Some("Stalled, 3 unknown\ncells"),
+            ),
+        ]))
+        .unwrap();
+        assert_eq!(expected.len(), 2);
+        assert_eq!(expected["a,b.txt"], BatchStatus::Solved);
+        assert_eq!(expected["\"c\".txt"], BatchStatus::Stalled);
+    }
+}
@@ -4,7 +4,7 @@ use std::fmt::Display;
 
 pub use vec2::Vec2;
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct Board<T> {
     values: Vec<T>,
     size: Vec2,
@@ -0,0 +1,213 @@
+use std::time::{Duration, Instant};
+
+use crate::solver::solver_display::{SolverDisplay, SolverState};
+
+/// How often progress updates reach one display of a [`CompositeDisplay`]. State changes and
+/// the last update of a line always do.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Throttle {
+    #[default]
+    Off,
+    /// Only updates whose placement number is a multiple of this.
+    Steps(usize),
+    /// At most one update per interval.
+    Interval(Duration),
+}
+
+impl Throttle {
+    /// What the console displays use unless told otherwise.
+    pub const DEFAULT: Throttle = Throttle::Interval(Duration::from_millis(50));
+}
+
+/// Applies a [`Throttle`] to the progress updates of one display.
+pub(crate) struct ProgressFilter {
+    throttle: Throttle,
+    last_update: Option<Instant>,
+}
+
+impl ProgressFilter {
+    pub fn new(throttle: Throttle) -> Self {
+        Self {
+            throttle,
+            last_update: None,
+        }
+    }
+
+    pub fn passes(&mut self, progress: (usize, usize)) -> bool {
+        if progress.0 == progress.1 {
+            return true;
+        }
+        match self.throttle {
+            Throttle::Off => true,
+            Throttle::Steps(steps) => progress.0.is_multiple_of(steps.max(1)),
+            Throttle::Interval(interval) => {
+                let now = Instant::now();
+                if self
+                    .last_update
+                    .is_some_and(|last| now.duration_since(last) < interval)
+                {
+                    return false;
+                }
+                self.last_update = Some(now);
+                true
+            }
+        }
+    }
+}
+
+struct Observer {
+    display: Box<dyn SolverDisplay>,
+    filter: ProgressFilter,
+}
+
+/// Sends every update to several displays, each with its own [`Throttle`].
+pub struct CompositeDisplay {
+    observers: Vec<Observer>,
+}
+
+impl CompositeDisplay {
+    pub fn builder() -> CompositeDisplayBuilder {
+        CompositeDisplayBuilder {
+            observers: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.observers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.observers.is_empty()
+    }
+}
+
+impl SolverDisplay for CompositeDisplay {
+    fn change_state(&mut self, state: SolverState) {
+        if let Some((last, rest)) = self.observers.split_last_mut() {
+            for observer in rest {
+                observer.display.change_state(state.clone());
+            }
+            last.display.change_state(state);
+        }
+    }
+
+    fn update_progress(&mut self, progress: (usize, usize)) {
+        for observer in &mut self.observers {
+            if observer.filter.passes(progress) {
+                observer.display.update_progress(progress);
+            }
+        }
+    }
+
+    fn set_hints(&mut self, row_hints: &[Vec<usize>], column_hints: &[Vec<usize>]) {
+        for observer in &mut self.observers {
+            observer.display.set_hints(row_hints, column_hints);
+        }
+    }
+
+    fn wants_heatmap(&self) -> bool {
+        self.observers
+            .iter()
+            .any(|observer| observer.display.wants_heatmap())
+    }
+
+    fn wants_waiting_lines(&self) -> bool {
+        self.observers
+            .iter()
+            .any(|observer| observer.display.wants_waiting_lines())
+    }
+}
+
+pub struct CompositeDisplayBuilder {
+    observers: Vec<Observer>,
+}
+
+impl CompositeDisplayBuilder {
+    /// Adds a display that gets every progress update.
+    pub fn with(self, display: Box<dyn SolverDisplay>) -> Self {
+        self.with_throttled(display, Throttle::Off)
+    }
+
+    pub fn with_throttled(mut self, display: Box<dyn SolverDisplay>, throttle: Throttle) -> Self {
+        self.observers.push(Observer {
+            display,
+            filter: ProgressFilter::new(throttle),
+        });
+        self
+    }
+
+    pub fn build(self) -> CompositeDisplay {
+        CompositeDisplay {
+            observers: self.observers,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Log {
+        states: usize,
+        progress: Vec<(usize, usize)>,
+    }
+
+    struct Recorder(Arc<Mutex<Log>>);
+
+    impl SolverDisplay for Recorder {
+        fn change_state(&mut self, _state: SolverState) {
+            self.0.lock().unwrap().states += 1;
+        }
+
+        fn update_progress(&mut self, progress: (usize, usize)) {
+            self.0.lock().unwrap().progress.push(progress);
+        }
+    }
+
+    #[test]
+    fn test_throttles() {
+        let logs: [Arc<Mutex<Log>>; 3] = Default::default();
+        let mut display = CompositeDisplay::builder()
+            .with(Box::new(Recorder(logs[0].clone())))
+            .with_throttled(Box::new(Recorder(logs[1].clone())), Throttle::Steps(4))
+            .with_throttled(
+                Box::new(Recorder(logs[2].clone())),
+                Throttle::Interval(Duration::from_secs(3600)),
+            )
+            .build();
+        assert_eq!(display.len(), 3);
+
+        display.change_state(SolverState::Idle);
+        for i in 1..=10 {
+            display.update_progress((i, 10));
+        }
+        display.change_state(SolverState::Solved);
+
+        let logs = logs.map(|log| std::mem::take(&mut *log.lock().unwrap()));
+        assert!(logs.iter().all(|log| log.states == 2));
+        assert_eq!(logs[0].progress.len(), 10);
+        assert_eq!(logs[1].progress, vec![(4, 10), (8, 10), (10, 10)]);
+        assert_eq!(logs[2].progress, vec![(1, 10), (10, 10)]);
+    }
+
+    #[test]
+    fn test_wants_waiting_lines() {
+        let log = Arc::new(Mutex::new(Log::default()));
+        let display = CompositeDisplay::builder()
+            .with(Box::new(Recorder(log.clone())))
+            .build();
+        assert!(!display.wants_waiting_lines());
+
+        let display = CompositeDisplay::builder()
+            .with(Box::new(Recorder(log)))
+            .with(Box::new(crate::display::ConsoleDisplay::with_writer(
+                0,
+                Box::new(std::io::sink()),
+            )))
+            .build();
+        assert!(display.wants_waiting_lines());
+    }
+}
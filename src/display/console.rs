@@ -1,130 +1,372 @@
-use crate::solver::solver_display::{SolverDisplay, SolverState};
+use super::screen::Screen;
+use super::{ProgressFilter, Throttle};
+use crate::board::{Board, Vec2};
+use crate::generator::line_hint;
+use crate::solver::solver_display::{SolverDisplay, SolverState, SolvingContext};
 use crate::solver::types::LineDirection;
-use std::thread;
-use std::time::Duration;
+use crate::solver::Cell;
+use std::io::{self, Write};
+
+const ACTIVE: &str = "\x1b[93m";
+const DONE: &str = "\x1b[90m";
+const BLANK: &str = "\x1b[2m";
+const UNKNOWN: &str = "\x1b[34m";
+const RESET: &str = "\x1b[0m";
+
+/// Cells between two grid separators.
+const GROUP: usize = 5;
 
 pub struct ConsoleDisplay {
-    is_new_screen: bool,
-    interval_ms: u64,
+    screen: Screen,
+    row_hints: Vec<Vec<usize>>,
+    column_hints: Vec<Vec<usize>>,
+    progress: ProgressFilter,
 }
 
 impl ConsoleDisplay {
     pub fn new(interval_ms: u64) -> Self {
+        Self::with_writer(interval_ms, Box::new(io::stderr()))
+    }
+
+    pub fn with_writer(interval_ms: u64, out: Box<dyn Write>) -> Self {
         Self {
-            is_new_screen: false,
-            interval_ms,
+            screen: Screen::new(interval_ms, out),
+            row_hints: Vec::new(),
+            column_hints: Vec::new(),
+            progress: ProgressFilter::new(Throttle::DEFAULT),
         }
     }
 
     pub fn new_with_default() -> Self {
         Self::new(0)
     }
-}
 
-impl SolverDisplay for ConsoleDisplay {
-    fn change_state(&mut self, state: SolverState) {
-        if matches!(state, SolverState::Solving(_)) {
-            if !self.is_new_screen {
-                self.is_new_screen = true;
-                print!("{esc}[?1049h {esc}[J {esc}[?25l", esc = 27 as char);
+    /// How often the progress bar is redrawn, [`Throttle::DEFAULT`] unless set.
+    pub fn with_throttle(mut self, throttle: Throttle) -> Self {
+        self.progress = ProgressFilter::new(throttle);
+        self
+    }
+
+    /// Draws the board with its clues, one string per terminal line.
+    fn render(&self, context: &SolvingContext) -> Vec<String> {
+        let board = &context.board;
+        let size = board.size();
+        let row_hint = |row: usize| hint_text(self.row_hints.get(row).map(Vec::as_slice));
+        let column_hint = |column: usize| {
+            self.column_hints
+                .get(column)
+                .map(|hint| hint.iter().map(usize::to_string).collect::<Vec<_>>())
+                .unwrap_or_default()
+        };
+
+        let clue_width = (0..size.row)
+            .map(|row| row_hint(row).len())
+            .max()
+            .unwrap_or(0);
+        let clue_height = (0..size.column)
+            .map(|column| column_hint(column).len())
+            .max()
+            .unwrap_or(0);
+        // 가장 긴 열 힌트 숫자에 한 칸을 띄워 이웃 숫자와 붙지 않게 한다
+        let cell_width = (0..size.column)
+            .flat_map(column_hint)
+            .map(|number| number.len() + 1)
+            .max()
+            .unwrap_or(0)
+            .max(2);
+
+        let row_done = |row: usize| {
+            is_satisfied(
+                board.iter_row(row),
+                self.row_hints.get(row).map(Vec::as_slice),
+            )
+        };
+        let column_done = |column: usize| {
+            is_satisfied(
+                board.iter_column(column),
+                self.column_hints.get(column).map(Vec::as_slice),
+            )
+        };
+        let is_active = |direction: LineDirection, index: usize| {
+            context.line.direction() == direction && context.line.index() == index
+        };
+        let color = |active: bool, done: bool| match (active, done) {
+            (true, _) => ACTIVE,
+            (false, true) => DONE,
+            (false, false) => "",
+        };
+
+        let mut lines = Vec::new();
+        let margin = " ".repeat(clue_width + 1);
+
+        for depth in 0..clue_height {
+            let mut line = margin.clone();
+            for column in 0..size.column {
+                let hint = column_hint(column);
+                let number = (depth + hint.len())
+                    .checked_sub(clue_height)
+                    .map(|index| hint[index].as_str())
+                    .unwrap_or("");
+                let color = color(
+                    is_active(LineDirection::Column, column),
+                    column_done(column),
+                );
+                line.push_str(&paint(color, &format!("{:>cell_width$}", number)));
+                if is_separator(column, size.column) {
+                    line.push(' ');
+                }
             }
-        } else if self.is_new_screen {
-            self.is_new_screen = false;
-            print!("{esc}[J {esc}[?1049l {esc}[?25h", esc = 27 as char);
+            lines.push(line);
         }
 
-        match state {
-            SolverState::Loading(message) => println!("Loading... {}", message),
-            SolverState::Idle => println!("Ready to solve!"),
-            SolverState::Solving(solving_state) => {
-                print!("{esc}[H", esc = 27 as char);
-
-                let board = solving_state.board;
-
-                let board_string = board.to_string();
-                let board_lines: Vec<&str> = board_string.lines().collect();
-
-                for (row_idx, line) in board_lines.iter().enumerate() {
-                    print!("{esc}[K", esc = 27 as char);
-
-                    if solving_state.line.direction() == LineDirection::Row
-                        && row_idx == solving_state.line.index()
-                    {
-                        print!("\x1b[93m{}\x1b[0m", line);
-                        print!(" \x1b[93m←\x1b[0m");
-                    } else if solving_state.line.direction() == LineDirection::Column {
-                        let chars: Vec<char> = line.chars().collect();
-                        for (col_idx, ch) in chars.chunks(2).enumerate() {
-                            if col_idx == solving_state.line.index() {
-                                print!("\x1b[93m{}{}\x1b[0m", ch[0], ch[1]);
-                            } else {
-                                print!("{}{}", ch[0], ch[1]);
-                            }
-                        }
-                    } else {
-                        print!("{}  ", line);
-                    }
-                    println!();
+        for row in 0..size.row {
+            let active = is_active(LineDirection::Row, row);
+            let mut line = paint(
+                color(active, row_done(row)),
+                &format!("{:>clue_width$} ", row_hint(row)),
+            );
+            for column in 0..size.column {
+                let cell = *board.value(Vec2::new(row, column));
+                let highlighted = active || is_active(LineDirection::Column, column);
+                line.push_str(&cell_text(cell, cell_width, highlighted));
+                if is_separator(column, size.column) {
+                    line.push('│');
                 }
+            }
+            if active {
+                line.push_str(&paint(ACTIVE, " ←"));
+            }
+            lines.push(line);
 
-                print!("{esc}[K", esc = 27 as char);
-                if solving_state.line.direction() == LineDirection::Column {
-                    for i in 0..board.size().column {
-                        if i == solving_state.line.index() {
-                            print!("\x1b[93m↑ \x1b[0m");
-                        } else {
-                            print!("  ");
-                        }
+            if is_separator(row, size.row) {
+                let mut line = margin.clone();
+                for column in 0..size.column {
+                    line.push_str(&"─".repeat(cell_width));
+                    if is_separator(column, size.column) {
+                        line.push('┼');
                     }
                 }
+                lines.push(line);
+            }
+        }
 
-                println!();
-                println!();
-
-                print!("{esc}[K", esc = 27 as char);
-                solving_state.line_waiting.iter().take(10).for_each(|line| {
-                    print!(
-                        "{}{} ",
-                        line.index() + 1,
-                        match line.direction() {
-                            LineDirection::Row => "R",
-                            LineDirection::Column => "C",
-                        }
-                    )
-                });
-                if solving_state.line_waiting.len() > 10 {
-                    print!(" ...{} more", solving_state.line_waiting.len() - 10);
+        if context.line.direction() == LineDirection::Column {
+            let mut line = margin.clone();
+            for column in 0..size.column {
+                if is_active(LineDirection::Column, column) {
+                    line.push_str(&paint(ACTIVE, &format!("{:<cell_width$}", "↑")));
+                } else {
+                    line.push_str(&" ".repeat(cell_width));
                 }
-                println!();
-                println!();
-                if self.interval_ms > 0 {
-                    thread::sleep(Duration::from_millis(self.interval_ms));
+                if is_separator(column, size.column) {
+                    line.push(' ');
                 }
             }
-            SolverState::Solved => println!("Solved!"),
+            lines.push(line);
         }
+        lines
     }
+}
 
-    fn update_progress(&mut self, progress: (usize, usize)) {
-        if !self.is_new_screen {
+fn hint_text(hint: Option<&[usize]>) -> String {
+    hint.unwrap_or_default()
+        .iter()
+        .map(usize::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether every cell of a line is known and its filled runs match `hint`.
+fn is_satisfied<'a>(cells: impl Iterator<Item = &'a Cell>, hint: Option<&[usize]>) -> bool {
+    let cells = cells.collect::<Vec<_>>();
+    if cells.iter().any(|&&cell| cell == Cell::Unknown) {
+        return false;
+    }
+    let expected = hint
+        .unwrap_or_default()
+        .iter()
+        .copied()
+        .filter(|&number| number > 0);
+    line_hint(cells).into_iter().eq(expected)
+}
+
+/// Whether a separator follows cell `index` of a line of `length` cells.
+fn is_separator(index: usize, length: usize) -> bool {
+    (index + 1).is_multiple_of(GROUP) && index + 1 < length
+}
+
+fn paint(color: &str, text: &str) -> String {
+    if color.is_empty() {
+        text.to_string()
+    } else {
+        format!("{}{}{}", color, text, RESET)
+    }
+}
+
+fn cell_text(cell: Cell, width: usize, highlighted: bool) -> String {
+    match cell {
+        Cell::Block if highlighted => paint(ACTIVE, &"█".repeat(width)),
+        Cell::Block => "█".repeat(width),
+        Cell::Blank => paint(BLANK, &format!("{:>width$}", "·")),
+        Cell::Crash => paint(ACTIVE, &"X".repeat(width)),
+        Cell::Unknown if highlighted => paint(ACTIVE, &"░".repeat(width)),
+        Cell::Unknown => paint(UNKNOWN, &"░".repeat(width)),
+    }
+}
+
+impl SolverDisplay for ConsoleDisplay {
+    fn change_state(&mut self, state: SolverState) {
+        let Some(context) = self.screen.change_state(state) else {
             return;
+        };
+
+        let mut lines = self.render(&context);
+        lines.push(String::new());
+        let mut waiting = context
+            .line_waiting
+            .iter()
+            .map(|line| {
+                let direction = match line.direction() {
+                    LineDirection::Row => "R",
+                    LineDirection::Column => "C",
+                };
+                format!("{}{} ", line.index() + 1, direction)
+            })
+            .collect::<String>();
+        let more = context.queue_length - context.line_waiting.len();
+        if more > 0 {
+            waiting.push_str(&format!(" ...{} more", more));
         }
+        lines.push(waiting);
+        lines.push(String::new());
+        self.screen.draw(lines);
+    }
 
-        if progress.0 % 1991 != 0 && progress.0 != progress.1 {
-            return;
+    fn update_progress(&mut self, progress: (usize, usize)) {
+        if self.screen.is_open() && self.progress.passes(progress) {
+            self.screen.draw_progress(progress);
         }
+    }
+
+    fn wants_waiting_lines(&self) -> bool {
+        true
+    }
+
+    fn set_hints(&mut self, row_hints: &[Vec<usize>], column_hints: &[Vec<usize>]) {
+        self.row_hints = row_hints.to_vec();
+        self.column_hints = column_hints.to_vec();
+    }
+}
+
+/// Draws `board` like its `Display` output with the cells set in `highlighted`, e.g. the cells
+/// where solutions differ.
+pub fn highlight_cells(board: &Board<Cell>, highlighted: &Board<bool>) -> String {
+    let size = board.size();
+    let mut text = String::new();
+    for row in 0..size.row {
+        for column in 0..size.column {
+            let position = Vec2::new(row, column);
+            let cell = board.value(position);
+            if *highlighted.value(position) {
+                // 빈 칸도 보이도록 배경색을 쓴다
+                text.push_str(&format!("\x1b[93;100m{}\x1b[0m", cell));
+            } else {
+                text.push_str(&cell.to_string());
+            }
+        }
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::types::Line;
+
+    /// Removes ANSI colour codes.
+    fn plain(text: &str) -> String {
+        let mut result = String::new();
+        let mut chars = text.chars();
+        while let Some(ch) = chars.next() {
+            if ch == '\x1b' {
+                chars.by_ref().find(|&ch| ch == 'm');
+            } else {
+                result.push(ch);
+            }
+        }
+        result
+    }
+
+    fn context(board: Board<Cell>, line: Line) -> SolvingContext {
+        SolvingContext {
+            board,
+            line,
+            line_waiting: Vec::new(),
+            queue_length: 0,
+            heatmap: None,
+        }
+    }
+
+    #[test]
+    fn test_render_clues_and_separators() {
+        let mut display = ConsoleDisplay::with_writer(0, Box::new(io::sink()));
+        let row_hints = (0..6).map(|_| vec![1, 1]).collect::<Vec<_>>();
+        let column_hints = (0..6)
+            .map(|column| if column == 0 { vec![2, 1] } else { vec![12] })
+            .collect::<Vec<_>>();
+        display.set_hints(&row_hints, &column_hints);
+
+        let mut board = Board::new(Vec2::new(6, 6), Cell::Unknown);
+        *board.value_mut(Vec2::new(0, 0)) = Cell::Block;
+        *board.value_mut(Vec2::new(0, 1)) = Cell::Blank;
+        let context = context(board, Line::new(LineDirection::Row, 0));
+
+        let lines = display
+            .render(&context)
+            .iter()
+            .map(|line| plain(line).trim_end().to_string())
+            .collect::<Vec<_>>();
+        // 두 자리 숫자 때문에 칸이 세 글자가 된다
+        assert_eq!(lines[0], "      2");
+        assert_eq!(lines[1], "      1 12 12 12 12  12");
+        assert_eq!(lines[2], "1 1 ███  ·░░░░░░░░░│░░░ ←");
+        assert_eq!(lines[7], "    ───────────────┼───");
+        assert_eq!(lines.len(), 2 + 6 + 1);
+
+        display.set_hints(&row_hints, &vec![vec![1]; 6]);
+        let lines = display.render(&context);
+        assert_eq!(plain(&lines[0]).trim_end(), "     1 1 1 1 1  1");
+        assert_eq!(plain(&lines[1]).trim_end(), "1 1 ██ ·░░░░░░│░░ ←");
+    }
+
+    #[test]
+    fn test_completed_lines_are_grey() {
+        let mut display = ConsoleDisplay::with_writer(0, Box::new(io::sink()));
+        display.set_hints(&[vec![1], vec![0]], &[vec![1], vec![0]]);
+        let mut board = Board::new(Vec2::new(2, 2), Cell::Unknown);
+        *board.value_mut(Vec2::new(0, 0)) = Cell::Block;
+        *board.value_mut(Vec2::new(0, 1)) = Cell::Blank;
+        let lines = display.render(&context(board.clone(), Line::new(LineDirection::Column, 1)));
+        assert!(lines[1].starts_with(&format!("{}1 {}", DONE, RESET)));
+        assert!(!lines[2].contains(DONE));
+
+        // 칸이 모두 정해져도 힌트와 다르면 끝난 줄이 아니다
+        *board.value_mut(Vec2::new(0, 0)) = Cell::Blank;
+        let lines = display.render(&context(board, Line::new(LineDirection::Column, 1)));
+        assert!(!lines[1].contains(DONE));
+    }
 
-        let progress_length = 40;
-        let progress_count =
-            (((progress.0 as f64) / (progress.1 as f64)) * (progress_length as f64)) as usize;
-
-        print!("{esc}[K", esc = 27 as char);
-        print!(
-            "\r[{}{}] {}/{}",
-            "#".repeat(progress_count),
-            " ".repeat(progress_length - progress_count),
-            progress.0,
-            progress.1
+    #[test]
+    fn test_highlight_cells() {
+        let mut board = Board::new(Vec2::new(1, 2), Cell::Blank);
+        *board.value_mut(Vec2::new(0, 0)) = Cell::Block;
+        let mut highlighted = Board::new(Vec2::new(1, 2), false);
+        assert_eq!(highlight_cells(&board, &highlighted), board.to_string());
+        *highlighted.value_mut(Vec2::new(0, 1)) = true;
+        assert_eq!(
+            highlight_cells(&board, &highlighted),
+            "██\x1b[93;100m  \x1b[0m\n"
         );
     }
 }
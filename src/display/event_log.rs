@@ -0,0 +1,374 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+
+use crate::board::{Board, Vec2};
+use crate::solver::limits::AbortReason;
+use crate::solver::solver_display::{SolverDisplay, SolverState, SolvingContext};
+use crate::solver::types::{Line, LineDirection};
+use crate::solver::Cell;
+
+/// Writes every state change and progress update as one JSON object per line.
+///
+/// Each event has `time` in seconds since the display was created and an `event` name.
+/// Solving steps only carry the cells that changed since the previous step, so the first one
+/// also gives the board size. The hints are written once as a `hints` event.
+pub struct EventLogDisplay<W: Write = BufWriter<File>> {
+    out: W,
+    started: Instant,
+    last_board: Option<Board<Cell>>,
+}
+
+impl EventLogDisplay {
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> EventLogDisplay<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            started: Instant::now(),
+            last_board: None,
+        }
+    }
+
+    fn write_event(&mut self, mut event: Value) {
+        event["time"] = json!(self.started.elapsed().as_secs_f64());
+        let _ = writeln!(self.out, "{}", event);
+    }
+
+    fn solving_event(&mut self, context: &SolvingContext) -> Value {
+        let board = &context.board;
+        let size = board.size();
+        let mut changed = Vec::new();
+        for row in 0..size.row {
+            for column in 0..size.column {
+                let position = Vec2::new(row, column);
+                let cell = *board.value(position);
+                let old = match &self.last_board {
+                    Some(last) => *last.value(position),
+                    None => Cell::Unknown,
+                };
+                if cell != old {
+                    changed.push(json!([row, column, cell_str(cell)]));
+                }
+            }
+        }
+
+        let mut event = json!({
+            "event": "solving",
+            "line": line_json(context.line),
+            "queue": context.queue_length,
+            "waiting": context
+                .line_waiting
+                .iter()
+                .map(|&line| line_json(line))
+                .collect::<Vec<_>>(),
+            "changed": changed,
+        });
+        if self.last_board.is_none() {
+            event["rows"] = json!(size.row);
+            event["columns"] = json!(size.column);
+        }
+        self.last_board = Some(board.clone());
+        event
+    }
+}
+
+impl<W: Write> SolverDisplay for EventLogDisplay<W> {
+    fn change_state(&mut self, state: SolverState) {
+        let event = match &state {
+            SolverState::Loading(message) => json!({ "event": "loading", "message": message }),
+            SolverState::Idle => json!({ "event": "idle" }),
+            SolverState::Solving(context) => self.solving_event(context),
+            SolverState::Solved => json!({ "event": "solved" }),
+            SolverState::Aborted(reason) => {
+                json!({ "event": "aborted", "reason": reason_str(*reason) })
+            }
+        };
+        self.write_event(event);
+        if !matches!(state, SolverState::Solving(_)) {
+            let _ = self.out.flush();
+        }
+    }
+
+    fn update_progress(&mut self, progress: (usize, usize)) {
+        self.write_event(json!({
+            "event": "progress",
+            "done": progress.0,
+            "total": progress.1,
+        }));
+    }
+
+    fn set_hints(&mut self, row_hints: &[Vec<usize>], column_hints: &[Vec<usize>]) {
+        self.write_event(json!({
+            "event": "hints",
+            "row_hints": row_hints,
+            "column_hints": column_hints,
+        }));
+    }
+
+    fn wants_waiting_lines(&self) -> bool {
+        true
+    }
+}
+
+impl<W: Write> Drop for EventLogDisplay<W> {
+    fn drop(&mut self) {
+        let _ = self.out.flush();
+    }
+}
+
+fn cell_str(cell: Cell) -> &'static str {
+    match cell {
+        Cell::Block => "#",
+        Cell::Blank => ".",
+        Cell::Crash => "!",
+        Cell::Unknown => "?",
+    }
+}
+
+fn line_json(line: Line) -> Value {
+    let direction = match line.direction() {
+        LineDirection::Row => "row",
+        LineDirection::Column => "column",
+    };
+    json!({ "direction": direction, "index": line.index() })
+}
+
+const REASONS: [(AbortReason, &str); 4] = [
+    (AbortReason::Cancelled, "cancelled"),
+    (AbortReason::Timeout, "timeout"),
+    (AbortReason::LinePassLimit, "line_pass_limit"),
+    (AbortReason::PlacementLimit, "placement_limit"),
+];
+
+fn reason_str(reason: AbortReason) -> &'static str {
+    REASONS
+        .iter()
+        .find(|(r, _)| *r == reason)
+        .map_or("cancelled", |(_, name)| name)
+}
+
+fn parse_line(value: &Value) -> Option<Line> {
+    let direction = match value["direction"].as_str()? {
+        "row" => LineDirection::Row,
+        "column" => LineDirection::Column,
+        _ => return None,
+    };
+    Some(Line::new(direction, value["index"].as_u64()? as usize))
+}
+
+/// Sends the events of a log written by [`EventLogDisplay`] to `display`, waiting between them
+/// as long as the solver did divided by `speed`. A `speed` of 0 does not wait at all.
+pub fn replay_event_log(
+    log: impl BufRead,
+    display: &mut dyn SolverDisplay,
+    speed: f64,
+) -> Result<(), String> {
+    let mut board: Option<Board<Cell>> = None;
+    let mut last_time = None;
+
+    for (number, text) in log.lines().enumerate() {
+        let text = text.map_err(|e| format!("Failed to read event log: {}", e))?;
+        if text.trim().is_empty() {
+            continue;
+        }
+        let invalid = |message: &str| format!("Line {}: {}", number + 1, message);
+        let event: Value =
+            serde_json::from_str(&text).map_err(|e| invalid(&format!("not JSON: {}", e)))?;
+
+        if let Some(time) = event["time"].as_f64() {
+            if let Some(last_time) = last_time {
+                if speed > 0.0 && time > last_time {
+                    thread::sleep(Duration::from_secs_f64((time - last_time) / speed));
+                }
+            }
+            last_time = Some(time);
+        }
+
+        match event["event"].as_str() {
+            Some("loading") => display.change_state(SolverState::Loading(
+                event["message"].as_str().unwrap_or_default().to_string(),
+            )),
+            Some("idle") => display.change_state(SolverState::Idle),
+            Some("hints") => {
+                let (Some(row_hints), Some(column_hints)) = (
+                    hints_field(&event["row_hints"]),
+                    hints_field(&event["column_hints"]),
+                ) else {
+                    return Err(invalid("bad hints"));
+                };
+                display.set_hints(&row_hints, &column_hints);
+            }
+            Some("solving") => {
+                if let (Some(rows), Some(columns)) =
+                    (event["rows"].as_u64(), event["columns"].as_u64())
+                {
+                    board = Some(Board::new(
+                        Vec2::new(rows as usize, columns as usize),
+                        Cell::Unknown,
+                    ));
+                }
+                let board = board
+                    .as_mut()
+                    .ok_or_else(|| invalid("solving step before the board size"))?;
+                apply_changes(board, &event["changed"]).ok_or_else(|| invalid("bad changes"))?;
+
+                let line = parse_line(&event["line"]).ok_or_else(|| invalid("bad line"))?;
+                let line_waiting: Vec<Line> = event["waiting"]
+                    .as_array()
+                    .map(|lines| lines.iter().filter_map(parse_line).collect())
+                    .unwrap_or_default();
+                let queue_length = event["queue"]
+                    .as_u64()
+                    .map_or(line_waiting.len(), |length| length as usize);
+                display.change_state(SolverState::Solving(SolvingContext {
+                    board: board.clone(),
+                    line,
+                    queue_length: queue_length.max(line_waiting.len()),
+                    line_waiting,
+                    heatmap: None,
+                }));
+            }
+            Some("progress") => {
+                let (Some(done), Some(total)) = (event["done"].as_u64(), event["total"].as_u64())
+                else {
+                    return Err(invalid("bad progress"));
+                };
+                display.update_progress((done as usize, total as usize));
+            }
+            Some("solved") => display.change_state(SolverState::Solved),
+            Some("aborted") => {
+                let reason = REASONS
+                    .iter()
+                    .find(|(_, name)| event["reason"].as_str() == Some(name))
+                    .map(|(reason, _)| *reason)
+                    .ok_or_else(|| invalid("unknown abort reason"))?;
+                display.change_state(SolverState::Aborted(reason));
+            }
+            _ => return Err(invalid("unknown event")),
+        }
+    }
+    Ok(())
+}
+
+fn hints_field(value: &Value) -> Option<Vec<Vec<usize>>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|hint| {
+            hint.as_array()?
+                .iter()
+                .map(|number| number.as_u64().map(|number| number as usize))
+                .collect()
+        })
+        .collect()
+}
+
+fn apply_changes(board: &mut Board<Cell>, changed: &Value) -> Option<()> {
+    let size = board.size();
+    for change in changed.as_array()? {
+        let [row, column, cell] = change.as_array()?.as_slice() else {
+            return None;
+        };
+        let position = Vec2::new(row.as_u64()? as usize, column.as_u64()? as usize);
+        if position.row >= size.row || position.column >= size.column {
+            return None;
+        }
+        *board.value_mut(position) = match cell.as_str()? {
+            "#" => Cell::Block,
+            "." => Cell::Blank,
+            "!" => Cell::Crash,
+            "?" => Cell::Unknown,
+            _ => return None,
+        };
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::display::CompositeDisplay;
+    use crate::solver::parser::{FileSolverParser, SolverParser};
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Keeps the boards it is shown.
+    #[derive(Clone, Default)]
+    struct BoardCollector(Arc<Mutex<(Vec<Board<Cell>>, bool)>>);
+
+    impl SolverDisplay for BoardCollector {
+        fn change_state(&mut self, state: SolverState) {
+            let mut collected = self.0.lock().unwrap();
+            match state {
+                SolverState::Solving(context) => collected.0.push(context.board),
+                SolverState::Solved => collected.1 = true,
+                _ => {}
+            }
+        }
+
+        fn update_progress(&mut self, _progress: (usize, usize)) {}
+    }
+
+    #[test]
+    fn test_log_and_replay() {
+        let log = SharedBuffer::default();
+        let expected = BoardCollector::default();
+        let display = CompositeDisplay::builder()
+            .with(Box::new(EventLogDisplay::new(log.clone())))
+            .with(Box::new(expected.clone()))
+            .build();
+        let mut solver = FileSolverParser::new("./sample/data1.txt")
+            .create_solver(Box::new(display))
+            .unwrap();
+        solver.solve().unwrap();
+        drop(solver);
+
+        let text = String::from_utf8(log.0.lock().unwrap().clone()).unwrap();
+        assert!(text.lines().any(|line| line.contains("\"progress\"")));
+        assert!(text.lines().any(|line| line.contains("\"hints\"")));
+
+        let mut replayed = BoardCollector::default();
+        replay_event_log(text.as_bytes(), &mut replayed, 0.0).unwrap();
+        let (expected, replayed) = (expected.0.lock().unwrap(), replayed.0.lock().unwrap());
+        assert!(replayed.1);
+        assert_eq!(replayed.0.len(), expected.0.len());
+        for (replayed, expected) in replayed.0.iter().zip(&expected.0) {
+            assert!(replayed.iter_all().eq(expected.iter_all()));
+        }
+    }
+
+    #[test]
+    fn test_invalid_log() {
+        let mut display = BoardCollector::default();
+        assert!(replay_event_log(&b"{\"event\":\"solving\"}\n"[..], &mut display, 0.0).is_err());
+        assert!(replay_event_log(&b"nope\n"[..], &mut display, 0.0).is_err());
+        assert!(replay_event_log(
+            &b"{\"event\":\"aborted\",\"reason\":\"timeout\"}\n"[..],
+            &mut display,
+            0.0
+        )
+        .is_ok());
+    }
+}
@@ -0,0 +1,145 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use gif::{Encoder, Frame, Repeat};
+
+use super::raster::{BoardRenderer, PALETTE};
+use crate::board::Board;
+use crate::solver::solver_display::{SolverDisplay, SolverState};
+use crate::solver::Cell;
+
+/// Records every solving step as a frame of an animated GIF.
+pub struct GifRecorderDisplay<W: Write = BufWriter<File>> {
+    renderer: BoardRenderer,
+    frame_delay_cs: u16,
+    writer: Option<W>,
+    encoder: Option<Encoder<W>>,
+    last_board: Option<Board<Cell>>,
+}
+
+impl GifRecorderDisplay {
+    pub fn create<P: AsRef<Path>>(
+        path: P,
+        renderer: BoardRenderer,
+        frame_delay_cs: u16,
+    ) -> io::Result<Self> {
+        let file = File::create(path)?;
+        Ok(Self::new(BufWriter::new(file), renderer, frame_delay_cs))
+    }
+}
+
+impl<W: Write> GifRecorderDisplay<W> {
+    pub fn new(writer: W, renderer: BoardRenderer, frame_delay_cs: u16) -> Self {
+        Self {
+            renderer,
+            frame_delay_cs,
+            writer: Some(writer),
+            encoder: None,
+            last_board: None,
+        }
+    }
+
+    fn write_frame(&mut self, frame: Frame) -> io::Result<()> {
+        if self.encoder.is_none() {
+            let Some(writer) = self.writer.take() else {
+                return Ok(());
+            };
+            let mut encoder = Encoder::new(writer, frame.width, frame.height, &PALETTE)
+                .map_err(io::Error::other)?;
+            encoder
+                .set_repeat(Repeat::Infinite)
+                .map_err(io::Error::other)?;
+            self.encoder = Some(encoder);
+        }
+
+        if let Some(encoder) = self.encoder.as_mut() {
+            encoder.write_frame(&frame).map_err(io::Error::other)?;
+        }
+        Ok(())
+    }
+
+    fn record(&mut self, board: &Board<Cell>, state: &SolverState) -> io::Result<()> {
+        let highlight = match state {
+            SolverState::Solving(context) => Some(context.line),
+            _ => None,
+        };
+        let image = self.renderer.render(board, highlight);
+        let (Ok(width), Ok(height)) = (u16::try_from(image.width), u16::try_from(image.height))
+        else {
+            return Err(io::Error::other("Board is too large for a GIF frame"));
+        };
+
+        let mut frame = Frame::from_indexed_pixels(width, height, image.pixels, None);
+        frame.delay = self.frame_delay_cs;
+        self.write_frame(frame)
+    }
+
+    /// Writes the GIF trailer and returns the underlying writer.
+    pub fn finish(mut self) -> io::Result<Option<W>> {
+        match self.encoder.take() {
+            Some(encoder) => encoder.into_inner().map(Some).map_err(io::Error::other),
+            None => Ok(self.writer.take()),
+        }
+    }
+}
+
+impl<W: Write> SolverDisplay for GifRecorderDisplay<W> {
+    fn change_state(&mut self, state: SolverState) {
+        let result = match &state {
+            SolverState::Solving(context) => {
+                let result = self.record(&context.board, &state);
+                self.last_board = Some(context.board.clone());
+                result
+            }
+            SolverState::Solved => match self.last_board.take() {
+                Some(board) => self.record(&board, &state),
+                None => Ok(()),
+            },
+            _ => Ok(()),
+        };
+
+        if let Err(e) = result {
+            eprintln!("Failed to record frame: {}", e);
+            self.encoder = None;
+            self.writer = None;
+        }
+    }
+
+    fn update_progress(&mut self, _progress: (usize, usize)) {}
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::Vec2;
+    use crate::solver::solver_display::SolvingContext;
+    use crate::solver::types::{Line, LineDirection};
+
+    #[test]
+    fn test_record_frames() {
+        let mut display = GifRecorderDisplay::new(Vec::new(), BoardRenderer::new(2), 5);
+        let board = Board::new(Vec2::new(3, 4), Cell::Unknown);
+        for index in 0..3 {
+            display.change_state(SolverState::Solving(SolvingContext {
+                board: board.clone(),
+                line: Line::new(LineDirection::Row, index),
+                line_waiting: Vec::new(),
+                queue_length: 0,
+                heatmap: None,
+            }));
+        }
+        display.change_state(SolverState::Solved);
+
+        let buffer = display.finish().unwrap().unwrap();
+        assert_eq!(&buffer[..6], b"GIF89a");
+        assert_eq!(buffer.last(), Some(&0x3B));
+
+        let mut decoder = gif::DecodeOptions::new().read_info(&buffer[..]).unwrap();
+        let mut frames = 0;
+        while decoder.read_next_frame().unwrap().is_some() {
+            frames += 1;
+        }
+        assert_eq!(frames, 4);
+    }
+}
@@ -0,0 +1,125 @@
+use std::io::{self, Write};
+
+use super::screen::Screen;
+use super::{ProgressFilter, Throttle};
+use crate::board::{Board, Vec2};
+use crate::solver::heatmap::CellHeat;
+use crate::solver::solver_display::{SolverDisplay, SolverState};
+use crate::solver::Cell;
+
+/// 256-colour backgrounds from cold (no placement fills the cell) to hot (every one does).
+const HEAT_COLORS: [u8; 11] = [21, 27, 33, 39, 45, 51, 190, 226, 220, 208, 196];
+
+/// Shows each unknown cell in two halves, coloured by how many surviving placements of its row
+/// (left) and of its column (right) fill it.
+pub struct HeatmapDisplay {
+    screen: Screen,
+    progress: ProgressFilter,
+}
+
+impl HeatmapDisplay {
+    pub fn new(interval_ms: u64) -> Self {
+        Self::with_writer(interval_ms, Box::new(io::stderr()))
+    }
+
+    pub fn with_writer(interval_ms: u64, out: Box<dyn Write>) -> Self {
+        Self {
+            screen: Screen::new(interval_ms, out),
+            progress: ProgressFilter::new(Throttle::DEFAULT),
+        }
+    }
+
+    /// How often the progress bar is redrawn, [`Throttle::DEFAULT`] unless set.
+    pub fn with_throttle(mut self, throttle: Throttle) -> Self {
+        self.progress = ProgressFilter::new(throttle);
+        self
+    }
+}
+
+fn heat_color(ratio: f32) -> u8 {
+    let last = HEAT_COLORS.len() - 1;
+    HEAT_COLORS[((ratio.clamp(0.0, 1.0) * last as f32).round() as usize).min(last)]
+}
+
+/// One string per row.
+fn render(board: &Board<Cell>, heatmap: &Board<CellHeat>) -> Vec<String> {
+    let size = board.size();
+    (0..size.row)
+        .map(|row| {
+            (0..size.column)
+                .map(|column| {
+                    let position = Vec2::new(row, column);
+                    match board.value(position) {
+                        Cell::Block => "██".to_string(),
+                        Cell::Blank => "\x1b[2m ·\x1b[0m".to_string(),
+                        Cell::Crash => "XX".to_string(),
+                        Cell::Unknown => {
+                            let heat = heatmap.value(position);
+                            format!(
+                                "\x1b[48;5;{}m \x1b[48;5;{}m \x1b[0m",
+                                heat_color(heat.row),
+                                heat_color(heat.column)
+                            )
+                        }
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+impl SolverDisplay for HeatmapDisplay {
+    fn change_state(&mut self, state: SolverState) {
+        let Some(context) = self.screen.change_state(state) else {
+            return;
+        };
+        let Some(heatmap) = &context.heatmap else {
+            return;
+        };
+
+        let mut lines = render(&context.board, heatmap);
+        let legend = HEAT_COLORS
+            .iter()
+            .map(|color| format!("\x1b[48;5;{}m \x1b[0m", color))
+            .collect::<String>();
+        lines.push(String::new());
+        lines.push(format!(
+            "0 {} 1  left: row, right: column  solving {}",
+            legend, context.line
+        ));
+        self.screen.draw(lines);
+    }
+
+    fn update_progress(&mut self, progress: (usize, usize)) {
+        if self.screen.is_open() && self.progress.passes(progress) {
+            self.screen.draw_progress(progress);
+        }
+    }
+
+    fn wants_heatmap(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let mut board = Board::new(Vec2::new(1, 3), Cell::Unknown);
+        *board.value_mut(Vec2::new(0, 0)) = Cell::Block;
+        *board.value_mut(Vec2::new(0, 1)) = Cell::Blank;
+        let mut heatmap = Board::new(Vec2::new(1, 3), CellHeat::default());
+        *heatmap.value_mut(Vec2::new(0, 2)) = CellHeat {
+            row: 1.0,
+            column: 0.0,
+        };
+
+        assert_eq!(
+            render(&board, &heatmap),
+            vec!["██\x1b[2m ·\x1b[0m\x1b[48;5;196m \x1b[48;5;21m \x1b[0m".to_string()]
+        );
+        assert_eq!(heat_color(0.5), 51);
+    }
+}
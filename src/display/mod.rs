@@ -1,5 +1,29 @@
+mod composite;
 mod console;
+mod event_log;
+mod gif_recorder;
+mod heatmap;
+mod progress_bar;
+mod raster;
+mod screen;
 mod simple_console;
 
-pub use console::ConsoleDisplay;
+use composite::ProgressFilter;
+pub use composite::{CompositeDisplay, CompositeDisplayBuilder, Throttle};
+pub use console::{highlight_cells, ConsoleDisplay};
+pub use event_log::{replay_event_log, EventLogDisplay};
+pub use gif_recorder::GifRecorderDisplay;
+pub use heatmap::HeatmapDisplay;
+pub use progress_bar::ProgressBarDisplay;
+pub use raster::{BoardRenderer, RasterImage, PALETTE};
 pub use simple_console::SimpleConsoleDisplay;
+
+use crate::solver::solver_display::{SolverDisplay, SolverState};
+
+/// Display that ignores every update, for solvers running in the background or with `--quiet`.
+pub struct NullDisplay;
+
+impl SolverDisplay for NullDisplay {
+    fn change_state(&mut self, _state: SolverState) {}
+    fn update_progress(&mut self, _progress: (usize, usize)) {}
+}
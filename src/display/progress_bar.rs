@@ -0,0 +1,165 @@
+use std::io::{self, Write};
+
+use super::{ProgressFilter, Throttle};
+use crate::solver::solver_display::{SolverDisplay, SolverState};
+use crate::solver::types::Line;
+
+const BAR_LENGTH: usize = 40;
+
+/// `[####    ] done/total` for the placements of the line being solved.
+pub(crate) fn progress_bar(progress: (usize, usize)) -> String {
+    let filled = (progress.0.min(progress.1) * BAR_LENGTH)
+        .checked_div(progress.1)
+        .unwrap_or(BAR_LENGTH);
+    format!(
+        "[{}{}] {}/{}",
+        "#".repeat(filled),
+        " ".repeat(BAR_LENGTH - filled),
+        progress.0,
+        progress.1
+    )
+}
+
+/// Keeps a single line up to date with the line being solved and how far along it is, for
+/// terminals where the full board does not fit or is not wanted.
+pub struct ProgressBarDisplay {
+    out: Box<dyn Write>,
+    progress: ProgressFilter,
+    line: Option<Line>,
+    queue_length: usize,
+}
+
+impl ProgressBarDisplay {
+    pub fn new() -> Self {
+        Self::with_writer(Box::new(io::stderr()))
+    }
+
+    pub fn with_writer(out: Box<dyn Write>) -> Self {
+        Self {
+            out,
+            progress: ProgressFilter::new(Throttle::DEFAULT),
+            line: None,
+            queue_length: 0,
+        }
+    }
+
+    /// How often the bar is redrawn, [`Throttle::DEFAULT`] unless set.
+    pub fn with_throttle(mut self, throttle: Throttle) -> Self {
+        self.progress = ProgressFilter::new(throttle);
+        self
+    }
+
+    /// Ends the bar so that a message can be written below it.
+    fn finish(&mut self) {
+        if self.line.take().is_some() {
+            let _ = writeln!(self.out);
+        }
+    }
+}
+
+impl Default for ProgressBarDisplay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SolverDisplay for ProgressBarDisplay {
+    fn change_state(&mut self, state: SolverState) {
+        match state {
+            SolverState::Solving(context) => {
+                self.line = Some(context.line);
+                self.queue_length = context.queue_length;
+            }
+            SolverState::Loading(message) => {
+                self.finish();
+                let _ = writeln!(self.out, "Loading... {}", message);
+            }
+            SolverState::Idle => {}
+            SolverState::Solved => {
+                self.finish();
+                let _ = writeln!(self.out, "Solved!");
+            }
+            SolverState::Aborted(reason) => {
+                self.finish();
+                let _ = writeln!(self.out, "Aborted: {}", reason);
+            }
+        }
+    }
+
+    fn update_progress(&mut self, progress: (usize, usize)) {
+        let Some(line) = self.line else {
+            return;
+        };
+        if !self.progress.passes(progress) {
+            return;
+        }
+        let _ = write!(
+            self.out,
+            "\r{esc}[K{}{} ...{} queued",
+            line,
+            progress_bar(progress),
+            self.queue_length,
+            esc = 27 as char
+        );
+        let _ = self.out.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::board::{Board, Vec2};
+    use crate::solver::solver_display::SolvingContext;
+    use crate::solver::types::LineDirection;
+    use crate::solver::Cell;
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_progress_bar() {
+        assert_eq!(
+            progress_bar((1, 4)),
+            format!("[{}{}] 1/4", "#".repeat(10), " ".repeat(30))
+        );
+        assert_eq!(progress_bar((0, 0)), format!("[{}] 0/0", "#".repeat(40)));
+    }
+
+    #[test]
+    fn test_one_line() {
+        let out = SharedBuffer::default();
+        let mut display =
+            ProgressBarDisplay::with_writer(Box::new(out.clone())).with_throttle(Throttle::Off);
+        display.change_state(SolverState::Idle);
+        display.update_progress((1, 2));
+        display.change_state(SolverState::Solving(SolvingContext {
+            board: Board::new(Vec2::new(2, 2), Cell::Unknown),
+            line: Line::new(LineDirection::Row, 0),
+            line_waiting: Vec::new(),
+            queue_length: 3,
+            heatmap: None,
+        }));
+        display.update_progress((1, 2));
+        display.update_progress((2, 2));
+        display.change_state(SolverState::Solved);
+
+        let text = String::from_utf8(out.0.lock().unwrap().clone()).unwrap();
+        let lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with(&format!("{} ...3 queued", progress_bar((2, 2)))));
+        assert_eq!(text.matches('\r').count(), 2);
+        assert_eq!(lines[1], "Solved!");
+    }
+}
@@ -0,0 +1,145 @@
+use std::io::{self, Write};
+
+use crate::board::{Board, Vec2};
+use crate::solver::types::{Line, LineDirection};
+use crate::solver::Cell;
+
+const BLANK: u8 = 0;
+const BLOCK: u8 = 1;
+const UNKNOWN: u8 = 2;
+const CRASH: u8 = 3;
+const GRID: u8 = 4;
+const HIGHLIGHT_BLANK: u8 = 5;
+const HIGHLIGHT_BLOCK: u8 = 6;
+const HIGHLIGHT_UNKNOWN: u8 = 7;
+
+pub const PALETTE: [u8; 24] = [
+    0xFF, 0xFF, 0xFF, // blank
+    0x10, 0x10, 0x10, // block
+    0xB0, 0xB0, 0xB0, // unknown
+    0xE0, 0x30, 0x30, // crash
+    0xD8, 0xD8, 0xD8, // grid
+    0xFF, 0xF3, 0xA0, // highlighted blank
+    0x5A, 0x48, 0x00, // highlighted block
+    0xE8, 0xC8, 0x40, // highlighted unknown
+];
+
+/// Palette-indexed image of a board. Indices refer to [`PALETTE`].
+pub struct RasterImage {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+pub struct BoardRenderer {
+    cell_size: usize,
+}
+
+impl BoardRenderer {
+    pub fn new(cell_size: usize) -> Self {
+        Self {
+            cell_size: cell_size.max(1),
+        }
+    }
+
+    pub fn new_with_default() -> Self {
+        Self::new(8)
+    }
+
+    pub fn image_size(&self, board_size: Vec2) -> Vec2 {
+        // 셀 사이와 바깥 테두리에 1px 격자선
+        Vec2::new(
+            board_size.row * (self.cell_size + 1) + 1,
+            board_size.column * (self.cell_size + 1) + 1,
+        )
+    }
+
+    pub fn render(&self, board: &Board<Cell>, highlight: Option<Line>) -> RasterImage {
+        let size = self.image_size(board.size());
+        let mut pixels = vec![GRID; size.row * size.column];
+
+        for row in 0..board.size().row {
+            for column in 0..board.size().column {
+                let highlighted = highlight.is_some_and(|line| match line.direction() {
+                    LineDirection::Row => line.index() == row,
+                    LineDirection::Column => line.index() == column,
+                });
+                let color = match (board.value(Vec2::new(row, column)), highlighted) {
+                    (Cell::Blank, false) => BLANK,
+                    (Cell::Block, false) => BLOCK,
+                    (Cell::Unknown, false) => UNKNOWN,
+                    (Cell::Blank, true) => HIGHLIGHT_BLANK,
+                    (Cell::Block, true) => HIGHLIGHT_BLOCK,
+                    (Cell::Unknown, true) => HIGHLIGHT_UNKNOWN,
+                    (Cell::Crash, _) => CRASH,
+                };
+
+                let top = row * (self.cell_size + 1) + 1;
+                let left = column * (self.cell_size + 1) + 1;
+                for y in top..top + self.cell_size {
+                    let start = y * size.column + left;
+                    pixels[start..start + self.cell_size].fill(color);
+                }
+            }
+        }
+
+        RasterImage {
+            width: size.column,
+            height: size.row,
+            pixels,
+        }
+    }
+
+    pub fn write_png<W: Write>(&self, board: &Board<Cell>, writer: W) -> io::Result<()> {
+        let image = self.render(board, None);
+
+        let mut encoder = png::Encoder::new(writer, image.width as u32, image.height as u32);
+        encoder.set_color(png::ColorType::Indexed);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_palette(&PALETTE[..]);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&image.pixels)?;
+        writer.finish()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_render_size() {
+        let board = Board::new(Vec2::new(2, 3), Cell::Unknown);
+        let image = BoardRenderer::new(4).render(&board, None);
+        assert_eq!(image.width, 3 * 5 + 1);
+        assert_eq!(image.height, 2 * 5 + 1);
+        assert_eq!(image.pixels.len(), image.width * image.height);
+    }
+
+    #[test]
+    fn test_render_highlight() {
+        let mut board = Board::new(Vec2::new(2, 2), Cell::Blank);
+        *board.value_mut(Vec2::new(1, 1)) = Cell::Block;
+        let image = BoardRenderer::new(1).render(&board, Some(Line::new(LineDirection::Row, 1)));
+
+        // 1px 셀: (row, column) 셀은 (2 * row + 1, 2 * column + 1) 픽셀
+        let pixel =
+            |row: usize, column: usize| image.pixels[(2 * row + 1) * image.width + 2 * column + 1];
+        assert_eq!(pixel(0, 0), BLANK);
+        assert_eq!(pixel(1, 0), HIGHLIGHT_BLANK);
+        assert_eq!(pixel(1, 1), HIGHLIGHT_BLOCK);
+        assert_eq!(image.pixels[0], GRID);
+    }
+
+    #[test]
+    fn test_write_png() {
+        let board = Board::new(Vec2::new(3, 3), Cell::Block);
+        let mut buffer = Vec::new();
+        BoardRenderer::new_with_default()
+            .write_png(&board, &mut buffer)
+            .unwrap();
+        assert_eq!(&buffer[..8], b"\x89PNG\r\n\x1a\n");
+    }
+}
@@ -0,0 +1,119 @@
+use std::io::Write;
+use std::thread;
+use std::time::Duration;
+
+use super::progress_bar::progress_bar;
+use crate::solver::solver_display::{SolverState, SolvingContext};
+
+/// Alternate terminal screen shared by the displays that redraw the whole board.
+///
+/// The screen is entered on the first solving step and left on any other state, whose message
+/// is written to the normal screen.
+pub(crate) struct Screen {
+    is_open: bool,
+    interval_ms: u64,
+    pub out: Box<dyn Write>,
+}
+
+impl Screen {
+    pub fn new(interval_ms: u64, out: Box<dyn Write>) -> Self {
+        Self {
+            is_open: false,
+            interval_ms,
+            out,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    /// Handles `state`, except for solving steps, which are returned to be drawn.
+    pub fn change_state(&mut self, state: SolverState) -> Option<SolvingContext> {
+        if matches!(state, SolverState::Solving(_)) {
+            if !self.is_open {
+                self.is_open = true;
+                let _ = write!(
+                    self.out,
+                    "{esc}[?1049h {esc}[J {esc}[?25l",
+                    esc = 27 as char
+                );
+            }
+        } else if self.is_open {
+            self.is_open = false;
+            let _ = write!(
+                self.out,
+                "{esc}[J {esc}[?1049l {esc}[?25h",
+                esc = 27 as char
+            );
+        }
+
+        match state {
+            SolverState::Loading(message) => {
+                let _ = writeln!(self.out, "Loading... {}", message);
+            }
+            SolverState::Idle => {
+                let _ = writeln!(self.out, "Ready to solve!");
+            }
+            SolverState::Solving(context) => return Some(context),
+            SolverState::Solved => {
+                let _ = writeln!(self.out, "Solved!");
+            }
+            SolverState::Aborted(reason) => {
+                let _ = writeln!(self.out, "Aborted: {}", reason);
+            }
+        }
+        None
+    }
+
+    /// Replaces the screen with `lines`, then waits for the interval.
+    pub fn draw(&mut self, lines: impl IntoIterator<Item = String>) {
+        let _ = write!(self.out, "{esc}[H", esc = 27 as char);
+        for line in lines {
+            let _ = writeln!(self.out, "{esc}[K{}", line, esc = 27 as char);
+        }
+        if self.interval_ms > 0 {
+            thread::sleep(Duration::from_millis(self.interval_ms));
+        }
+    }
+
+    /// Overwrites the line below the last drawing with a progress bar.
+    pub fn draw_progress(&mut self, progress: (usize, usize)) {
+        let _ = write!(
+            self.out,
+            "\r{esc}[K{}",
+            progress_bar(progress),
+            esc = 27 as char
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+    use crate::board::{Board, Vec2};
+    use crate::solver::types::{Line, LineDirection};
+    use crate::solver::Cell;
+
+    #[test]
+    fn test_open_while_solving() {
+        let mut screen = Screen::new(0, Box::new(io::sink()));
+        assert!(screen.change_state(SolverState::Idle).is_none());
+        assert!(!screen.is_open());
+
+        let context = SolvingContext {
+            board: Board::new(Vec2::new(1, 1), Cell::Unknown),
+            line: Line::new(LineDirection::Row, 0),
+            line_waiting: Vec::new(),
+            queue_length: 0,
+            heatmap: None,
+        };
+        assert!(screen.change_state(SolverState::Solving(context)).is_some());
+        assert!(screen.is_open());
+
+        assert!(screen.change_state(SolverState::Solved).is_none());
+        assert!(!screen.is_open());
+    }
+}
@@ -1,55 +1,68 @@
-use std::{thread::sleep, time::Duration};
+use std::{
+    io::{self, Write},
+    thread::sleep,
+    time::Duration,
+};
 
-use crate::solver::solver_display::SolverDisplay;
+use super::progress_bar::progress_bar;
+use super::{ProgressFilter, Throttle};
+use crate::solver::solver_display::{SolverDisplay, SolverState};
 
 pub struct SimpleConsoleDisplay {
     interval_ms: u64,
+    out: Box<dyn Write>,
+    progress: ProgressFilter,
 }
 
 impl SimpleConsoleDisplay {
     pub fn new(interval_ms: u64) -> Self {
-        Self { interval_ms }
+        Self::with_writer(interval_ms, Box::new(io::stderr()))
     }
 
     pub fn new_with_default() -> Self {
         Self::new(0)
     }
+
+    pub fn with_writer(interval_ms: u64, out: Box<dyn Write>) -> Self {
+        Self {
+            interval_ms,
+            out,
+            progress: ProgressFilter::new(Throttle::DEFAULT),
+        }
+    }
+
+    /// How often the progress bar is redrawn, [`Throttle::DEFAULT`] unless set.
+    pub fn with_throttle(mut self, throttle: Throttle) -> Self {
+        self.progress = ProgressFilter::new(throttle);
+        self
+    }
 }
 
 impl SolverDisplay for SimpleConsoleDisplay {
-    fn change_state(&mut self, state: crate::solver::solver_display::SolverState) {
-        match state {
-            crate::solver::solver_display::SolverState::Loading(message) => {
-                println!("Loading... {}", message)
-            }
-            crate::solver::solver_display::SolverState::Idle => println!("Ready to solve!"),
-            crate::solver::solver_display::SolverState::Solving(solving_context) => {
-                print!("{esc}[K", esc = 27 as char);
-                println!(
+    fn change_state(&mut self, state: SolverState) {
+        let _ = match state {
+            SolverState::Loading(message) => writeln!(self.out, "Loading... {}", message),
+            SolverState::Idle => writeln!(self.out, "Ready to solve!"),
+            SolverState::Solving(solving_context) => {
+                let _ = write!(self.out, "{esc}[K", esc = 27 as char);
+                let result = writeln!(
+                    self.out,
                     "{} ... {}",
-                    solving_context.line,
-                    solving_context.line_waiting.len()
+                    solving_context.line, solving_context.queue_length
                 );
                 sleep(Duration::from_millis(self.interval_ms));
+                result
             }
-            crate::solver::solver_display::SolverState::Solved => println!("Solved!"),
-        }
+            SolverState::Solved => writeln!(self.out, "Solved!"),
+            SolverState::Aborted(reason) => writeln!(self.out, "Aborted: {}", reason),
+        };
     }
 
     fn update_progress(&mut self, progress: (usize, usize)) {
-        if progress.0 % 1991 != 0 && progress.0 != progress.1 {
+        if !self.progress.passes(progress) {
             return;
         }
-        let progress_length = 40;
-        let progress_count =
-            (((progress.0 as f64) / (progress.1 as f64)) * (progress_length as f64)) as usize;
-
-        print!(
-            "[{}{}] {}/{}\r",
-            "#".repeat(progress_count),
-            " ".repeat(progress_length - progress_count),
-            progress.0,
-            progress.1
-        );
+
+        let _ = write!(self.out, "{}\r", progress_bar(progress));
     }
 }
@@ -0,0 +1,137 @@
+use crate::board::{Board, Vec2};
+use crate::solver::parser::SolverParseResult;
+use crate::solver::Cell;
+
+/// SplitMix64 generator, so that a puzzle can be regenerated from its seed.
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Fills each cell independently with probability `density`.
+pub fn generate_board(size: Vec2, density: f64, random: &mut Random) -> Board<Cell> {
+    let mut board = Board::new(size, Cell::Blank);
+    for row in 0..size.row {
+        for column in 0..size.column {
+            if random.next_f64() < density {
+                *board.value_mut(Vec2::new(row, column)) = Cell::Block;
+            }
+        }
+    }
+    board
+}
+
+/// Lengths of the runs of filled cells, which is the hint of a finished line.
+pub fn line_hint<'a>(cells: impl IntoIterator<Item = &'a Cell>) -> Vec<usize> {
+    let mut hint = Vec::new();
+    let mut run = 0;
+    for cell in cells.into_iter().chain(std::iter::once(&Cell::Blank)) {
+        if *cell == Cell::Block {
+            run += 1;
+        } else if run > 0 {
+            hint.push(run);
+            run = 0;
+        }
+    }
+    hint
+}
+
+pub fn derive_hints(board: &Board<Cell>) -> SolverParseResult {
+    let size = board.size();
+    SolverParseResult {
+        board_size: size,
+        row_hints: (0..size.row)
+            .map(|row| line_hint(board.iter_row(row)))
+            .collect(),
+        column_hints: (0..size.column)
+            .map(|column| line_hint(board.iter_column(column)))
+            .collect(),
+    }
+}
+
+fn without_zero(hint: &[usize]) -> Vec<usize> {
+    hint.iter().copied().filter(|&n| n > 0).collect()
+}
+
+/// Indices of the rows and columns of `board` that do not match the hints of `puzzle`.
+pub fn find_wrong_lines(
+    puzzle: &SolverParseResult,
+    board: &Board<Cell>,
+) -> (Vec<usize>, Vec<usize>) {
+    let size = board.size();
+    let wrong_rows = (0..size.row)
+        .filter(|&row| line_hint(board.iter_row(row)) != without_zero(&puzzle.row_hints[row]))
+        .collect();
+    let wrong_columns = (0..size.column)
+        .filter(|&column| {
+            line_hint(board.iter_column(column)) != without_zero(&puzzle.column_hints[column])
+        })
+        .collect();
+    (wrong_rows, wrong_columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_hint() {
+        use Cell::*;
+        assert_eq!(line_hint(&[Block, Block, Blank, Block]), vec![2, 1]);
+        assert_eq!(line_hint(&[Blank, Blank]), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_generate_is_deterministic() {
+        let size = Vec2::new(5, 7);
+        let first = generate_board(size, 0.5, &mut Random::new(42));
+        let second = generate_board(size, 0.5, &mut Random::new(42));
+        assert!(first.iter_all().eq(second.iter_all()));
+    }
+
+    #[test]
+    fn test_derive_hints() {
+        let mut board = Board::new(Vec2::new(2, 3), Cell::Blank);
+        *board.value_mut(Vec2::new(0, 0)) = Cell::Block;
+        *board.value_mut(Vec2::new(0, 2)) = Cell::Block;
+        *board.value_mut(Vec2::new(1, 2)) = Cell::Block;
+
+        let hints = derive_hints(&board);
+        assert_eq!(hints.row_hints, vec![vec![1, 1], vec![1]]);
+        assert_eq!(hints.column_hints, vec![vec![1], vec![], vec![2]]);
+    }
+
+    #[test]
+    fn test_find_wrong_lines() {
+        let board = generate_board(Vec2::new(4, 5), 0.5, &mut Random::new(3));
+        let puzzle = derive_hints(&board);
+        assert_eq!(find_wrong_lines(&puzzle, &board), (vec![], vec![]));
+
+        let mut changed = board.clone();
+        let cell = changed.value_mut(Vec2::new(1, 2));
+        *cell = if *cell == Cell::Block {
+            Cell::Blank
+        } else {
+            Cell::Block
+        };
+        assert_eq!(find_wrong_lines(&puzzle, &changed), (vec![1], vec![2]));
+    }
+}
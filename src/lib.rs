@@ -1,5 +1,10 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 
+pub mod batch;
 pub mod board;
 pub mod display;
+pub mod generator;
+pub mod play;
+pub mod rating;
+pub mod server;
 pub mod solver;
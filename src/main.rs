@@ -1,60 +1,1287 @@
-use clap::Parser;
+use std::ffi::OsString;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
 use nonogram_solver::{
-    display::{ConsoleDisplay, SimpleConsoleDisplay},
+    batch::{self, BatchFormat},
+    board::{Board, Vec2},
+    display::{
+        highlight_cells, replay_event_log, BoardRenderer, CompositeDisplay, ConsoleDisplay,
+        EventLogDisplay, GifRecorderDisplay, HeatmapDisplay, NullDisplay, ProgressBarDisplay,
+        SimpleConsoleDisplay, Throttle,
+    },
+    generator::{self, Random},
+    play::{self, PlayState},
+    rating,
+    server::{self, ServerConfig},
     solver::{
-        parser::{FileSolverParser, HtmlTableSolverParser, SolverParser},
+        calculator::NumberDistributionCalculator,
+        error::SolverError,
+        limits::SolveLimits,
+        parser::{
+            AutoSolverParser, HtmlTableSolverParser, SolverParseResult, SolverParser,
+            TextSolverParser,
+        },
+        probing::{self, SolveLevel},
+        sat::SatSolver,
+        scheduler::LineHeuristic,
         solver_display::SolverDisplay,
+        Cell, Solver,
     },
 };
+use serde_json::json;
 
 #[derive(Parser)]
-struct Args {
+#[command(
+    arg_required_else_help = true,
+    after_help = "`nonogram_solver <INPUT_PATH>` is the same as `nonogram_solver solve <INPUT_PATH>`.
+
+Exit codes:
+  0  Solved, or the command succeeded
+  1  Any other error
+  2  Invalid command line arguments
+  3  Stalled: line logic left unknown cells
+  4  Contradiction: the hints cannot be satisfied
+  5  Invalid input: the hints do not fit the board
+  6  Parse error: the puzzle file could not be read or parsed
+  7  Timeout: a time, line pass or placement limit was reached"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Args)]
+struct InputArgs {
     input_path: String,
 
+    #[command(flatten)]
+    format: FormatArgs,
+}
+
+#[derive(Args)]
+struct FormatArgs {
+    /// Format of the puzzle file
+    #[arg(long, value_enum, default_value_t = InputFormat::Auto)]
+    format: InputFormat,
+
+    /// Same as `--format html`
     #[arg(long, default_value_t = false)]
     html: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum InputFormat {
+    Auto,
+    Text,
+    Html,
+}
+
+impl InputArgs {
+    fn parse(&self) -> Result<SolverParseResult, CliError> {
+        self.format.parse_file(&self.input_path)
+    }
+}
+
+impl FormatArgs {
+    fn parse_file(&self, path: &str) -> Result<SolverParseResult, CliError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CliError::new(
+                ExitStatus::ParseError,
+                format!("Failed to read file: {}", e),
+            )
+        })?;
+        match (self.html, self.format) {
+            (true, _) | (false, InputFormat::Html) => HtmlTableSolverParser::new(&content).parse(),
+            (false, InputFormat::Text) => TextSolverParser::new(&content).parse(),
+            (false, InputFormat::Auto) => AutoSolverParser::new(&content).parse(),
+        }
+        .map_err(|e| CliError::new(ExitStatus::ParseError, e))
+    }
+}
+
+#[derive(Args)]
+struct DisplayArgs {
+    /// How the solving process is shown
+    #[arg(long, value_enum, default_value_t = DisplayMode::Console)]
+    display: DisplayMode,
+
+    /// Same as `--display simple`
+    #[arg(long, default_value_t = false)]
+    simple: bool,
 
     #[arg(short, long, default_value_t = 0)]
     interval: u64,
 
+    /// Shortest time between two progress bar updates in milliseconds
+    #[arg(long, default_value_t = 50)]
+    progress_interval: u64,
+
+    /// How progress is written to stderr: the board drawn by `--display`, a single progress bar
+    /// line, one line per solved line, or nothing
+    #[arg(long, value_enum, default_value_t = Progress::Board)]
+    progress: Progress,
+
+    /// Same as `--progress none`, so that only the result is written
+    #[arg(short, long, default_value_t = false, conflicts_with = "progress")]
+    quiet: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Progress {
+    None,
+    Board,
+    Bar,
+    Lines,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum DisplayMode {
+    Console,
+    Simple,
+    /// Colour unknown cells by how many surviving placements fill them
+    Heatmap,
+}
+
+impl DisplayArgs {
+    /// Writes progress to `out`, which is stderr outside tests so that stdout only has the
+    /// result.
+    fn create_display(&self, out: Box<dyn Write>) -> Box<dyn SolverDisplay> {
+        let throttle = Throttle::Interval(Duration::from_millis(self.progress_interval));
+        let mode = match self.progress {
+            _ if self.quiet => return Box::new(NullDisplay),
+            Progress::None => return Box::new(NullDisplay),
+            Progress::Bar => {
+                return Box::new(ProgressBarDisplay::with_writer(out).with_throttle(throttle))
+            }
+            Progress::Lines => DisplayMode::Simple,
+            Progress::Board if self.simple => DisplayMode::Simple,
+            Progress::Board => self.display,
+        };
+        match mode {
+            DisplayMode::Console => {
+                Box::new(ConsoleDisplay::with_writer(self.interval, out).with_throttle(throttle))
+            }
+            DisplayMode::Simple => Box::new(
+                SimpleConsoleDisplay::with_writer(self.interval, out).with_throttle(throttle),
+            ),
+            DisplayMode::Heatmap => {
+                Box::new(HeatmapDisplay::with_writer(self.interval, out).with_throttle(throttle))
+            }
+        }
+    }
+}
+
+#[derive(Args)]
+struct LimitArgs {
+    /// Stop solving after this many seconds
+    #[arg(long)]
+    timeout: Option<f64>,
+
+    /// Stop solving after this many line passes
+    #[arg(long)]
+    max_line_passes: Option<usize>,
+
+    /// Stop solving after trying this many line placements
+    #[arg(long)]
+    max_placements: Option<usize>,
+}
+
+impl LimitArgs {
+    fn to_limits(&self) -> SolveLimits {
+        SolveLimits {
+            deadline: self
+                .timeout
+                .map(|seconds| Instant::now() + Duration::from_secs_f64(seconds)),
+            max_line_passes: self.max_line_passes,
+            max_placements: self.max_placements,
+        }
+    }
+}
+
+#[derive(Args)]
+struct OutputArgs {
+    /// Format of the result
+    #[arg(long, visible_alias = "result-format", value_enum, default_value_t = OutputFormat::Text)]
+    output_format: OutputFormat,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Args)]
+struct SolveArgs {
+    /// Puzzle file, not needed with `--resume`
+    #[arg(required_unless_present = "resume")]
+    input_path: Option<String>,
+
+    #[command(flatten)]
+    format: FormatArgs,
+
+    /// Continue from a state saved with `--checkpoint` instead of reading a puzzle
+    #[arg(long, conflicts_with = "input_path")]
+    resume: Option<String>,
+
+    /// Save the solver state to this file while solving, to continue later with `--resume`
+    #[arg(long)]
+    checkpoint: Option<String>,
+
+    /// Seconds between two saves of the checkpoint
+    #[arg(long, default_value_t = 60, requires = "checkpoint")]
+    checkpoint_every: u64,
+
+    /// Also write every solving event to this file as JSON Lines, for `replay`
+    #[arg(long)]
+    event_log: Option<String>,
+
+    #[command(flatten)]
+    display: DisplayArgs,
+
+    #[command(flatten)]
+    limits: LimitArgs,
+
+    #[command(flatten)]
+    output: OutputArgs,
+
+    /// Write the final board as a PNG image
+    #[arg(long)]
+    png: Option<String>,
+
+    /// Record the solving process as an animated GIF instead of printing it
+    #[arg(long)]
+    gif: Option<String>,
+
+    /// Cell size in pixels for PNG/GIF output
+    #[arg(long, default_value_t = 8)]
+    cell_size: usize,
+
+    /// Remember up to this many deduced lines and reuse them for lines in the same state
+    #[arg(long, default_value_t = 0)]
+    line_cache: usize,
+
+    /// How the puzzle is solved. Only `--timeout` limits the SAT engine
+    #[arg(long, value_enum, default_value_t = Engine::Line)]
+    engine: Engine,
+
+    /// What the line engine does once line logic alone makes no more progress
+    #[arg(long, value_enum, default_value_t = Level::Line)]
+    level: Level,
+
+    /// How the next line to solve is chosen [default: fewest-possibilities, or the one saved
+    /// in the checkpoint with `--resume`]
+    #[arg(long, value_enum)]
+    heuristic: Option<Heuristic>,
+
+    /// Delay between GIF frames in 1/100 seconds
+    #[arg(long, default_value_t = 5)]
+    frame_delay: u16,
+
+    /// Solve all queued lines of one direction at once on multiple threads
+    #[cfg(feature = "parallel")]
     #[arg(long, default_value_t = false)]
-    simple: bool,
+    parallel: bool,
 }
 
-fn main() -> Result<(), String> {
-    let args = Args::parse();
+#[derive(Subcommand)]
+enum Command {
+    /// Solve the puzzle with line logic
+    Solve(SolveArgs),
+    /// Check that the puzzle is well-formed, or that a solution matches its hints
+    Check {
+        #[command(flatten)]
+        input: InputArgs,
+
+        /// File with one row per line, `#` for filled and `.` for empty cells
+        #[arg(long)]
+        solution: Option<String>,
+
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Count the possible placements of each line
+    Count {
+        #[command(flatten)]
+        input: InputArgs,
+
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Convert the puzzle to another format
+    Convert {
+        #[command(flatten)]
+        input: InputArgs,
+
+        #[command(flatten)]
+        output: OutputArgs,
+
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output_path: Option<String>,
+    },
+    /// Generate a random puzzle
+    Generate {
+        #[arg(long, default_value_t = 10)]
+        rows: usize,
+
+        #[arg(long, default_value_t = 10)]
+        columns: usize,
+
+        /// Probability that a cell is filled
+        #[arg(long, default_value_t = 0.5)]
+        density: f64,
+
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Also write the solution, one row per line
+        #[arg(long)]
+        solution: Option<String>,
+
+        #[command(flatten)]
+        output: OutputArgs,
+
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output_path: Option<String>,
+    },
+    /// Find several solutions and show the cells where they differ
+    Solutions {
+        #[command(flatten)]
+        input: InputArgs,
 
-    let display: Box<dyn SolverDisplay> = if args.simple {
-        Box::new(SimpleConsoleDisplay::new(args.interval))
+        /// Stop after finding this many solutions
+        #[arg(
+            long,
+            default_value_t = 2,
+            value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+        )]
+        limit: usize,
+
+        #[command(flatten)]
+        limits: LimitArgs,
+
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Rate how hard the puzzle is for line logic
+    Rate {
+        #[command(flatten)]
+        input: InputArgs,
+
+        #[command(flatten)]
+        limits: LimitArgs,
+
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Solve the puzzle and render the result as a PNG image
+    Render {
+        #[command(flatten)]
+        input: InputArgs,
+
+        #[command(flatten)]
+        limits: LimitArgs,
+
+        /// PNG file to write
+        #[arg(short, long)]
+        output_path: String,
+
+        /// Cell size in pixels
+        #[arg(long, default_value_t = 8)]
+        cell_size: usize,
+    },
+    /// Show an event log written by `solve --event-log` again
+    Replay {
+        /// Event log file
+        log_path: String,
+
+        /// How many times faster than the recorded solve to play; 0 plays without waiting
+        #[arg(long, default_value_t = 1.0)]
+        speed: f64,
+    },
+    /// Play the puzzle in the terminal
+    Play {
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Serve the solver over HTTP on localhost
+    Serve {
+        #[arg(short, long, default_value_t = 8080)]
+        port: u16,
+
+        /// Largest accepted request body in bytes
+        #[arg(long, default_value_t = 1 << 20)]
+        max_body_size: usize,
+
+        /// Time limit for solving a single request in seconds
+        #[arg(long, default_value_t = 10)]
+        timeout: u64,
+
+        /// Number of requests handled at the same time
+        #[arg(long, default_value_t = 4)]
+        workers: usize,
+    },
+    /// Solve every puzzle in a directory or matching a glob pattern
+    Batch {
+        /// Directory or glob pattern, e.g. "sample/**/*.txt"
+        input: String,
+
+        /// Number of puzzles solved at the same time
+        #[arg(short, long, default_value_t = 1)]
+        jobs: usize,
+
+        /// Time limit for each puzzle in seconds
+        #[arg(long)]
+        timeout: Option<f64>,
+
+        /// Format of the report
+        #[arg(long, value_enum, default_value_t = ReportFormat::Table)]
+        report_format: ReportFormat,
+
+        /// CSV file with expected statuses; exits with an error if any puzzle does worse
+        #[arg(long)]
+        expected: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ReportFormat {
+    Table,
+    Csv,
+    Json,
+}
+
+impl From<ReportFormat> for BatchFormat {
+    fn from(format: ReportFormat) -> Self {
+        match format {
+            ReportFormat::Table => BatchFormat::Table,
+            ReportFormat::Csv => BatchFormat::Csv,
+            ReportFormat::Json => BatchFormat::Json,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Engine {
+    /// Line logic, shown step by step
+    Line,
+    /// Complete search through a SAT encoding, which also solves puzzles beyond line logic
+    Sat,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Level {
+    /// Stop where line logic stops
+    Line,
+    /// Also try both values of each unknown cell and keep the one that does not contradict
+    Probe,
+    /// Probe, then guess and backtrack until the puzzle is solved
+    Search,
+}
+
+impl From<Level> for SolveLevel {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::Line => SolveLevel::Line,
+            Level::Probe => SolveLevel::Probe,
+            Level::Search => SolveLevel::Search,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Heuristic {
+    FewestPossibilities,
+    MostUnknownCells,
+    MostRecentlyChanged,
+    RoundRobin,
+    InformationGain,
+}
+
+impl From<Heuristic> for LineHeuristic {
+    fn from(heuristic: Heuristic) -> Self {
+        match heuristic {
+            Heuristic::FewestPossibilities => LineHeuristic::FewestPossibilities,
+            Heuristic::MostUnknownCells => LineHeuristic::MostUnknownCells,
+            Heuristic::MostRecentlyChanged => LineHeuristic::MostRecentlyChanged,
+            Heuristic::RoundRobin => LineHeuristic::RoundRobin,
+            Heuristic::InformationGain => LineHeuristic::InformationGain,
+        }
+    }
+}
+
+impl Command {
+    fn output_format(&self) -> OutputFormat {
+        match self {
+            Command::Solve(SolveArgs { output, .. })
+            | Command::Check { output, .. }
+            | Command::Count { output, .. }
+            | Command::Convert { output, .. }
+            | Command::Generate { output, .. }
+            | Command::Solutions { output, .. }
+            | Command::Rate { output, .. } => output.output_format,
+            _ => OutputFormat::Text,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum ExitStatus {
+    Solved = 0,
+    Error = 1,
+    Stalled = 3,
+    Contradiction = 4,
+    InvalidInput = 5,
+    ParseError = 6,
+    Timeout = 7,
+}
+
+impl ExitStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ExitStatus::Solved => "solved",
+            ExitStatus::Error => "error",
+            ExitStatus::Stalled => "stalled",
+            ExitStatus::Contradiction => "contradiction",
+            ExitStatus::InvalidInput => "invalid_input",
+            ExitStatus::ParseError => "parse_error",
+            ExitStatus::Timeout => "timeout",
+        }
+    }
+}
+
+struct CliError {
+    status: ExitStatus,
+    /// `None` when the error was already written as part of the result.
+    message: Option<String>,
+}
+
+impl CliError {
+    fn new(status: ExitStatus, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: Some(message.into()),
+        }
+    }
+
+    fn reported(status: ExitStatus) -> Self {
+        Self {
+            status,
+            message: None,
+        }
+    }
+}
+
+impl From<String> for CliError {
+    fn from(message: String) -> Self {
+        Self::new(ExitStatus::Error, message)
+    }
+}
+
+impl From<SolverError> for CliError {
+    fn from(e: SolverError) -> Self {
+        let status = match &e {
+            SolverError::InvalidBoardSize(..) | SolverError::InvalidInitialInfo(_) => {
+                ExitStatus::InvalidInput
+            }
+            SolverError::Contradiction(_) => ExitStatus::Contradiction,
+            SolverError::Aborted(_) => ExitStatus::Timeout,
+            SolverError::InvalidSolvingState(_) | SolverError::Checkpoint(_) => ExitStatus::Error,
+        };
+        Self::new(status, e.to_string())
+    }
+}
+
+/// Inserts `solve` when the first argument is not a subcommand, so that
+/// `nonogram_solver <file>` keeps working.
+fn with_default_command(mut args: Vec<OsString>) -> Vec<OsString> {
+    let Some(first) = args.get(1).and_then(|arg| arg.to_str()) else {
+        return args;
+    };
+    let is_command = first == "help"
+        || Cli::command()
+            .get_subcommands()
+            .any(|command| command.get_name() == first);
+    let is_help = matches!(first, "-h" | "--help");
+
+    if !is_command && !is_help {
+        args.insert(1, OsString::from("solve"));
+    }
+    args
+}
+
+fn write_output(text: &str, output_path: Option<&str>) -> Result<(), String> {
+    match output_path {
+        Some(path) => {
+            std::fs::write(path, text).map_err(|e| format!("Failed to write {}: {}", path, e))
+        }
+        None => {
+            print!("{}", text);
+            Ok(())
+        }
+    }
+}
+
+fn puzzle_json(puzzle: &SolverParseResult) -> serde_json::Value {
+    json!({
+        "rows": puzzle.board_size.row,
+        "columns": puzzle.board_size.column,
+        "row_hints": puzzle.row_hints,
+        "column_hints": puzzle.column_hints,
+    })
+}
+
+fn create_solver(
+    puzzle: SolverParseResult,
+    display: Box<dyn SolverDisplay>,
+) -> Result<Solver, CliError> {
+    Ok(Solver::new(
+        puzzle.board_size,
+        puzzle.row_hints,
+        puzzle.column_hints,
+        display,
+    )?)
+}
+
+/// What an engine leaves for the output of `solve`.
+struct SolveOutcome {
+    status: ExitStatus,
+    board: Board<Cell>,
+    error: Option<String>,
+    line_passes: Option<usize>,
+    line_cache: Option<serde_json::Value>,
+}
+
+fn solve(args: &SolveArgs) -> Result<(), CliError> {
+    let json = args.output.output_format == OutputFormat::Json;
+    let SolveOutcome {
+        status,
+        board,
+        error,
+        line_passes,
+        line_cache,
+    } = match args.engine {
+        Engine::Line => solve_with_lines(args)?,
+        Engine::Sat => solve_with_sat(args)?,
+    };
+
+    if let Some(png_path) = &args.png {
+        write_png(&board, png_path, args.cell_size)?;
+    }
+
+    if json {
+        println!(
+            "{}",
+            json!({
+                "status": status.as_str(),
+                "rows": board.size().row,
+                "columns": board.size().column,
+                "board": board.to_rows(),
+                "line_passes": line_passes,
+                "line_cache": line_cache,
+                "error": error,
+            })
+        );
     } else {
-        Box::new(ConsoleDisplay::new(args.interval))
+        println!("{}", board);
+    }
+
+    match error {
+        None => Ok(()),
+        Some(_) if json => Err(CliError::reported(status)),
+        Some(message) => Err(CliError::new(status, message)),
+    }
+}
+
+fn solve_with_lines(args: &SolveArgs) -> Result<SolveOutcome, CliError> {
+    let mut display =
+        CompositeDisplay::builder().with(args.display.create_display(Box::new(io::stderr())));
+    if let Some(gif_path) = &args.gif {
+        display = display.with(Box::new(
+            GifRecorderDisplay::create(
+                gif_path,
+                BoardRenderer::new(args.cell_size),
+                args.frame_delay,
+            )
+            .map_err(|e| format!("Failed to create GIF file: {}", e))?,
+        ));
+    }
+    if let Some(log_path) = &args.event_log {
+        display = display.with(Box::new(
+            EventLogDisplay::create(log_path)
+                .map_err(|e| format!("Failed to create event log: {}", e))?,
+        ));
+    }
+    let display = Box::new(display.build());
+
+    let mut solver = match (&args.resume, &args.input_path) {
+        (Some(state_path), _) => Solver::load_state_from(Path::new(state_path), display)?,
+        (None, Some(input_path)) => create_solver(args.format.parse_file(input_path)?, display)?,
+        (None, None) => unreachable!("clap requires an input path without --resume"),
     };
+    solver.set_limits(args.limits.to_limits());
+    match args.heuristic {
+        Some(heuristic) => solver.set_line_heuristic(heuristic.into()),
+        None if args.resume.is_none() => solver.set_line_heuristic(LineHeuristic::default()),
+        None => {}
+    }
+    solver.set_line_cache_capacity(args.line_cache);
+    solver.set_solve_level(args.level.into());
+    if let Some(checkpoint_path) = &args.checkpoint {
+        solver.set_checkpoint(checkpoint_path, Duration::from_secs(args.checkpoint_every));
+    }
 
-    let mut solver = if args.html {
-        HtmlTableSolverParser::new(
-            &std::fs::read_to_string(&args.input_path)
-                .map_err(|e| format!("Failed to read file: {}", e))?,
-        )
-        .create_solver(display)
+    #[cfg(feature = "parallel")]
+    let result = if args.parallel {
+        solver.solve_parallel()
+    } else {
+        solver.solve()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let result = solver.solve();
+
+    let (status, board, error) = match result {
+        Ok(()) if solver.is_solved() => (ExitStatus::Solved, solver.board().clone(), None),
+        Ok(()) => {
+            let unknown = solver
+                .board()
+                .iter_all()
+                .filter(|&&cell| cell == Cell::Unknown)
+                .count();
+            (
+                ExitStatus::Stalled,
+                solver.board().clone(),
+                Some(format!("Stalled with {} unknown cells", unknown)),
+            )
+        }
+        Err(SolverError::Aborted(e)) => {
+            if let Some(message) = &e.checkpoint_error {
+                eprintln!("Failed to save the checkpoint: {}", message);
+            }
+            (
+                ExitStatus::Timeout,
+                e.board,
+                Some(format!("Solving aborted: {}", e.reason)),
+            )
+        }
+        Err(e @ SolverError::Contradiction(_)) => (
+            ExitStatus::Contradiction,
+            solver.board().clone(),
+            Some(e.to_string()),
+        ),
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(SolveOutcome {
+        status,
+        board,
+        error,
+        line_passes: Some(solver.line_passes()),
+        line_cache: solver
+            .line_cache_stats()
+            .map(|stats| json!({ "hits": stats.hits, "misses": stats.misses })),
+    })
+}
+
+fn solve_with_sat(args: &SolveArgs) -> Result<SolveOutcome, CliError> {
+    let Some(input_path) = args.input_path.as_ref().filter(|_| args.resume.is_none()) else {
+        return Err(CliError::new(
+            ExitStatus::Error,
+            "--resume only works with --engine line",
+        ));
+    };
+    // 줄 단위 풀이에만 쓰이는 옵션은 조용히 무시하지 않는다
+    let line_only = [
+        ("--checkpoint", args.checkpoint.is_some()),
+        ("--event-log", args.event_log.is_some()),
+        ("--gif", args.gif.is_some()),
+        ("--heuristic", args.heuristic.is_some()),
+        ("--line-cache", args.line_cache != 0),
+        ("--level", args.level != Level::Line),
+        ("--max-line-passes", args.limits.max_line_passes.is_some()),
+        ("--max-placements", args.limits.max_placements.is_some()),
+        #[cfg(feature = "parallel")]
+        ("--parallel", args.parallel),
+    ];
+    if let Some((flag, _)) = line_only.iter().find(|(_, is_set)| *is_set) {
+        return Err(CliError::new(
+            ExitStatus::Error,
+            format!("{} only works with --engine line", flag),
+        ));
+    }
+
+    let puzzle = args.format.parse_file(input_path)?;
+    let size = puzzle.board_size;
+    let mut solver = SatSolver::new(size, puzzle.row_hints, puzzle.column_hints)?;
+    solver.set_limits(args.limits.to_limits());
+
+    let (status, board, error) = match solver.solve() {
+        Ok(Some(board)) => (ExitStatus::Solved, board, None),
+        Ok(None) => (
+            ExitStatus::Contradiction,
+            Board::new(size, Cell::Unknown),
+            Some("The hints have no solution".to_string()),
+        ),
+        Err(SolverError::Aborted(e)) => (
+            ExitStatus::Timeout,
+            e.board,
+            Some(format!("Solving aborted: {}", e.reason)),
+        ),
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(SolveOutcome {
+        status,
+        board,
+        error,
+        line_passes: None,
+        line_cache: None,
+    })
+}
+
+fn write_png(board: &Board<Cell>, png_path: &str, cell_size: usize) -> Result<(), String> {
+    let file =
+        std::fs::File::create(png_path).map_err(|e| format!("Failed to create PNG file: {}", e))?;
+    BoardRenderer::new(cell_size)
+        .write_png(board, std::io::BufWriter::new(file))
+        .map_err(|e| format!("Failed to write PNG file: {}", e))
+}
+
+fn check(input: &InputArgs, solution: Option<&str>, output: &OutputArgs) -> Result<(), CliError> {
+    let puzzle = input.parse()?;
+    let row_total = puzzle.row_hints.iter().flatten().sum::<usize>();
+    let column_total = puzzle.column_hints.iter().flatten().sum::<usize>();
+
+    let mut problems = Vec::new();
+    if let Err(e) = Solver::new(
+        puzzle.board_size,
+        puzzle.row_hints.clone(),
+        puzzle.column_hints.clone(),
+        Box::new(NullDisplay),
+    ) {
+        problems.push(e.to_string());
+    }
+    if row_total != column_total {
+        problems.push(format!(
+            "Row hints fill {} cells but column hints fill {}",
+            row_total, column_total
+        ));
+    }
+
+    let wrong_lines = if problems.is_empty() {
+        solution
+            .map(|path| {
+                let content = std::fs::read_to_string(path)
+                    .map_err(|e| format!("Failed to read solution: {}", e))?;
+                let rows = content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .collect::<Vec<_>>();
+                let board = Board::from_rows(puzzle.board_size, &rows)
+                    .map_err(|e| CliError::new(ExitStatus::ParseError, e))?;
+                Ok::<_, CliError>(generator::find_wrong_lines(&puzzle, &board))
+            })
+            .transpose()?
     } else {
-        FileSolverParser::new(&args.input_path).create_solver(display)
-    }?;
+        None
+    };
+
+    let status = match &wrong_lines {
+        _ if !problems.is_empty() => "invalid",
+        Some((rows, columns)) if !rows.is_empty() || !columns.is_empty() => "incorrect",
+        Some(_) => "correct",
+        None => "valid",
+    };
+
+    match output.output_format {
+        OutputFormat::Text => {
+            println!(
+                "{}x{} puzzle: {}",
+                puzzle.board_size.row, puzzle.board_size.column, status
+            );
+            for problem in &problems {
+                println!("  {}", problem);
+            }
+            if let Some((rows, columns)) = &wrong_lines {
+                for row in rows {
+                    println!("  Row {} does not match its hint", row + 1);
+                }
+                for column in columns {
+                    println!("  Column {} does not match its hint", column + 1);
+                }
+            }
+        }
+        OutputFormat::Json => {
+            let (wrong_rows, wrong_columns) = wrong_lines.unwrap_or_default();
+            println!(
+                "{}",
+                json!({
+                    "status": status,
+                    "rows": puzzle.board_size.row,
+                    "columns": puzzle.board_size.column,
+                    "problems": problems,
+                    "wrong_rows": wrong_rows,
+                    "wrong_columns": wrong_columns,
+                })
+            );
+        }
+    }
+
+    match status {
+        "valid" | "correct" => Ok(()),
+        "invalid" => Err(CliError::new(ExitStatus::InvalidInput, "Puzzle is invalid")),
+        _ => Err(CliError::new(ExitStatus::Error, "Solution is incorrect")),
+    }
+}
+
+fn count(input: &InputArgs, output: &OutputArgs) -> Result<(), CliError> {
+    let puzzle = input.parse()?;
+    let mut calculator = NumberDistributionCalculator::new();
+    let mut count_lines = |hints: &[Vec<usize>], length: usize| {
+        hints
+            .iter()
+            .map(|hint| calculator.calc_distribute_count_line_hint(hint, length))
+            .collect::<Vec<_>>()
+    };
+    let rows = count_lines(&puzzle.row_hints, puzzle.board_size.column);
+    let columns = count_lines(&puzzle.column_hints, puzzle.board_size.row);
+
+    match output.output_format {
+        OutputFormat::Text => {
+            for (index, count) in rows.iter().enumerate() {
+                println!("row {}: {}", index + 1, count);
+            }
+            for (index, count) in columns.iter().enumerate() {
+                println!("column {}: {}", index + 1, count);
+            }
+        }
+        OutputFormat::Json => println!("{}", json!({ "rows": rows, "columns": columns })),
+    }
+    Ok(())
+}
+
+fn convert(
+    input: &InputArgs,
+    output: &OutputArgs,
+    output_path: Option<&str>,
+) -> Result<(), CliError> {
+    let puzzle = input.parse()?;
+    let text = match output.output_format {
+        OutputFormat::Text => puzzle.to_text(),
+        OutputFormat::Json => format!("{}\n", puzzle_json(&puzzle)),
+    };
+    Ok(write_output(&text, output_path)?)
+}
+
+fn generate(
+    size: Vec2,
+    density: f64,
+    seed: Option<u64>,
+    solution: Option<&str>,
+    output: &OutputArgs,
+    output_path: Option<&str>,
+) -> Result<(), CliError> {
+    if size.row == 0 || size.column == 0 {
+        return Err(CliError::new(
+            ExitStatus::InvalidInput,
+            "Size must be at least 1x1",
+        ));
+    }
+    if !(0.0..=1.0).contains(&density) {
+        return Err(CliError::new(
+            ExitStatus::InvalidInput,
+            "Density must be between 0 and 1",
+        ));
+    }
+    let seed = seed.unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_nanos() as u64)
+            .unwrap_or_default()
+    });
+
+    let board = generator::generate_board(size, density, &mut Random::new(seed));
+    let puzzle = generator::derive_hints(&board);
+
+    if let Some(path) = solution {
+        let mut rows = board.to_rows().join("\n");
+        rows.push('\n');
+        write_output(&rows, Some(path))?;
+    }
+
+    let text = match output.output_format {
+        OutputFormat::Text => puzzle.to_text(),
+        OutputFormat::Json => {
+            let mut value = puzzle_json(&puzzle);
+            value["seed"] = json!(seed);
+            format!("{}\n", value)
+        }
+    };
+    Ok(write_output(&text, output_path)?)
+}
+
+fn solutions(
+    input: &InputArgs,
+    limit: usize,
+    limits: &LimitArgs,
+    output: &OutputArgs,
+) -> Result<(), CliError> {
+    let mut solver = create_solver(input.parse()?, Box::new(NullDisplay))?;
+    solver.set_limits(limits.to_limits());
+    let solutions = solver.enumerate_solutions(limit)?;
+    let differing = probing::differing_cells(&solutions);
+    // 한도에 닿으면 해가 더 있을 수 있다
+    let complete = solutions.len() < limit;
+
+    match output.output_format {
+        OutputFormat::Text => {
+            if let Some(first) = solutions.first() {
+                let mut highlighted = Board::new(first.size(), false);
+                for &position in &differing {
+                    *highlighted.value_mut(position) = true;
+                }
+                for (index, board) in solutions.iter().enumerate() {
+                    println!("Solution {}:", index + 1);
+                    println!("{}", highlight_cells(board, &highlighted));
+                }
+            }
+            match (solutions.len(), complete) {
+                (0, _) => println!("No solution"),
+                (1, true) => println!("The solution is unique"),
+                (count, true) => println!("{} solutions", count),
+                (count, false) => println!("At least {} solutions", count),
+            }
+            if !differing.is_empty() {
+                println!("{} cells differ", differing.len());
+            }
+        }
+        OutputFormat::Json => println!(
+            "{}",
+            json!({
+                "solutions": solutions.iter().map(|board| board.to_rows()).collect::<Vec<_>>(),
+                "complete": complete,
+                "differing_cells": differing
+                    .iter()
+                    .map(|position| [position.row, position.column])
+                    .collect::<Vec<_>>(),
+            })
+        ),
+    }
+
+    if solutions.is_empty() {
+        return Err(CliError::new(
+            ExitStatus::Contradiction,
+            "The puzzle has no solution",
+        ));
+    }
+    Ok(())
+}
+
+fn rate(input: &InputArgs, limits: &LimitArgs, output: &OutputArgs) -> Result<(), CliError> {
+    let rating = rating::rate(&input.parse()?, limits.to_limits())?;
+    match output.output_format {
+        OutputFormat::Text => {
+            println!("difficulty: {}", rating.difficulty);
+            println!("line passes: {}", rating.line_passes);
+            println!("passes per line: {:.2}", rating.passes_per_line);
+            println!("placements: {}", rating.placements);
+        }
+        OutputFormat::Json => println!(
+            "{}",
+            json!({
+                "difficulty": rating.difficulty.as_str(),
+                "solved": rating.solved,
+                "line_passes": rating.line_passes,
+                "passes_per_line": rating.passes_per_line,
+                "placements": rating.placements,
+            })
+        ),
+    }
+    Ok(())
+}
 
-    solver
-        .solve()
-        .map_err(|e| format!("Failed to solve: {:?}", e))?;
+fn render(
+    input: &InputArgs,
+    limits: &LimitArgs,
+    output_path: &str,
+    cell_size: usize,
+) -> Result<(), CliError> {
+    let mut solver = create_solver(input.parse()?, Box::new(NullDisplay))?;
+    solver.set_limits(limits.to_limits());
+    let board = match solver.solve() {
+        Ok(()) => solver.board().clone(),
+        Err(SolverError::Aborted(e)) => e.board,
+        Err(e) => return Err(e.into()),
+    };
+    Ok(write_png(&board, output_path, cell_size)?)
+}
+
+fn run_batch(
+    input: &str,
+    jobs: usize,
+    timeout: Option<f64>,
+    format: ReportFormat,
+    expected: Option<&str>,
+) -> Result<(), CliError> {
+    let expected = expected
+        .map(|path| {
+            std::fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read expected results: {}", e))
+                .and_then(|content| batch::parse_expected(&content))
+        })
+        .transpose()?;
+
+    let paths = batch::collect_inputs(input)?;
+    if paths.is_empty() {
+        return Err(format!("No puzzle found in {}", input).into());
+    }
 
-    let result = solver.board.to_string();
-    drop(solver);
-    println!("{}", result);
+    let entries = batch::run_batch(&paths, timeout.map(Duration::from_secs_f64), jobs);
+    print!("{}", batch::format_entries(&entries, format.into()));
+
+    if let Some(expected) = expected {
+        let regressions = batch::find_regressions(&entries, &expected);
+        for (entry, expected) in &regressions {
+            eprintln!(
+                "Regression: {} is {} (expected {})",
+                entry.file, entry.status, expected
+            );
+        }
+        if !regressions.is_empty() {
+            return Err(format!("{} puzzle(s) regressed", regressions.len()).into());
+        }
+    }
     Ok(())
 }
 
+fn replay(log_path: &str, speed: f64) -> Result<(), CliError> {
+    if !(speed >= 0.0 && speed.is_finite()) {
+        return Err(CliError::new(
+            ExitStatus::InvalidInput,
+            "Speed must be a number of at least 0",
+        ));
+    }
+    let file =
+        std::fs::File::open(log_path).map_err(|e| format!("Failed to open event log: {}", e))?;
+    let mut display = ConsoleDisplay::new_with_default();
+    replay_event_log(io::BufReader::new(file), &mut display, speed)
+        .map_err(|e| CliError::new(ExitStatus::ParseError, e))
+}
+
+fn play(input: &InputArgs) -> Result<(), CliError> {
+    let puzzle = input.parse()?;
+    let mut state = PlayState::new(puzzle.board_size, puzzle.row_hints, puzzle.column_hints)?;
+    Ok(play::tui::run(&mut state).map_err(|e| format!("Terminal error: {}", e))?)
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse_from(with_default_command(std::env::args_os().collect()));
+
+    match run(&cli.command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            if let Some(message) = &e.message {
+                match cli.command.output_format() {
+                    OutputFormat::Text => eprintln!("Error: {}", message),
+                    OutputFormat::Json => println!(
+                        "{}",
+                        json!({ "status": e.status.as_str(), "board": null, "error": message })
+                    ),
+                }
+            }
+            ExitCode::from(e.status as u8)
+        }
+    }
+}
+
+fn run(command: &Command) -> Result<(), CliError> {
+    match command {
+        Command::Solve(args) => solve(args),
+        Command::Check {
+            input,
+            solution,
+            output,
+        } => check(input, solution.as_deref(), output),
+        Command::Count { input, output } => count(input, output),
+        Command::Convert {
+            input,
+            output,
+            output_path,
+        } => convert(input, output, output_path.as_deref()),
+        Command::Generate {
+            rows,
+            columns,
+            density,
+            seed,
+            solution,
+            output,
+            output_path,
+        } => generate(
+            Vec2::new(*rows, *columns),
+            *density,
+            *seed,
+            solution.as_deref(),
+            output,
+            output_path.as_deref(),
+        ),
+        Command::Solutions {
+            input,
+            limit,
+            limits,
+            output,
+        } => solutions(input, *limit, limits, output),
+        Command::Rate {
+            input,
+            limits,
+            output,
+        } => rate(input, limits, output),
+        Command::Render {
+            input,
+            limits,
+            output_path,
+            cell_size,
+        } => render(input, limits, output_path, *cell_size),
+        Command::Replay { log_path, speed } => replay(log_path, *speed),
+        Command::Play { input } => play(input),
+        Command::Serve {
+            port,
+            max_body_size,
+            timeout,
+            workers,
+        } => Ok(server::serve(
+            ServerConfig {
+                port: *port,
+                max_body_size: *max_body_size,
+                solve_timeout: Duration::from_secs(*timeout),
+                workers: *workers,
+            },
+            |address| println!("Listening on http://{}", address),
+        )
+        .map_err(|e| format!("Server error: {}", e))?),
+        Command::Batch {
+            input,
+            jobs,
+            timeout,
+            report_format,
+            expected,
+        } => run_batch(input, *jobs, *timeout, *report_format, expected.as_deref()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::error::Error;
+    use std::sync::{Arc, Mutex};
 
     use super::*;
+    use nonogram_solver::solver::parser::FileSolverParser;
 
     fn solve_normal(filename: &str) -> Result<(), Box<dyn Error>> {
         let display = SimpleConsoleDisplay::new(0);
@@ -77,6 +1304,301 @@ mod tests {
         Ok(())
     }
 
+    fn parse_args(args: &[&str]) -> Result<Cli, clap::Error> {
+        let args = args.iter().map(OsString::from).collect();
+        Cli::try_parse_from(with_default_command(args))
+    }
+
+    #[test]
+    fn test_cli() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_default_command_is_solve() {
+        let Ok(Cli {
+            command: Command::Solve(args),
+        }) = parse_args(&["nonogram_solver", "sample/data1.txt", "--simple"])
+        else {
+            panic!("Expected the solve command");
+        };
+        assert_eq!(args.input_path.as_deref(), Some("sample/data1.txt"));
+        assert!(args.display.simple);
+
+        assert!(matches!(
+            parse_args(&["nonogram_solver", "--html", "table.txt"]),
+            Ok(Cli {
+                command: Command::Solve(_)
+            })
+        ));
+        assert!(matches!(
+            parse_args(&["nonogram_solver", "count", "sample/data1.txt"]),
+            Ok(Cli {
+                command: Command::Count { .. }
+            })
+        ));
+        assert!(parse_args(&["nonogram_solver", "--help"]).is_err());
+    }
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Solves the first sample with the display `args` ask for and returns what it wrote.
+    fn solve_to_buffer(args: &DisplayArgs) -> String {
+        let out = SharedBuffer::default();
+        let mut solver = FileSolverParser::new("./sample/data1.txt")
+            .create_solver(args.create_display(Box::new(out.clone())))
+            .unwrap();
+        solver.solve().unwrap();
+        drop(solver);
+        let text = out.0.lock().unwrap().clone();
+        String::from_utf8(text).unwrap()
+    }
+
+    #[test]
+    fn test_quiet_and_progress() {
+        let Ok(Cli {
+            command: Command::Solve(args),
+        }) = parse_args(&["nonogram_solver", "sample/data1.txt", "-q", "-i", "1000"])
+        else {
+            panic!("Expected the solve command");
+        };
+        assert!(args.display.quiet);
+        // 조용히 풀면 진행 상황을 하나도 쓰지 않는다
+        assert!(solve_to_buffer(&args.display).is_empty());
+
+        let Ok(Cli {
+            command: Command::Solve(args),
+        }) = parse_args(&["nonogram_solver", "sample/data1.txt", "--progress", "lines"])
+        else {
+            panic!("Expected the solve command");
+        };
+        assert!(args.display.progress == Progress::Lines);
+        let output = solve_to_buffer(&args.display);
+        assert!(output.ends_with("Solved!\n"));
+        assert!(output.lines().count() > 2);
+
+        assert!(parse_args(&[
+            "nonogram_solver",
+            "sample/data1.txt",
+            "--quiet",
+            "--progress",
+            "bar"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_checkpoint_and_resume() {
+        assert!(parse_args(&["nonogram_solver", "solve"]).is_err());
+        assert!(parse_args(&["nonogram_solver", "solve", "a.txt", "--resume", "b.json"]).is_err());
+
+        let state_path = std::env::temp_dir().join(format!(
+            "nonogram_solver_resume_{}.json",
+            std::process::id()
+        ));
+        let state_path = state_path.display().to_string();
+        let run = |args: &[&str]| {
+            let Ok(Cli {
+                command: Command::Solve(args),
+            }) = parse_args(args)
+            else {
+                panic!("Expected the solve command");
+            };
+            solve(&args).err().map(|e| e.status)
+        };
+
+        // 중간에 멈춰도 상태가 저장된다
+        let status = run(&[
+            "nonogram_solver",
+            "sample/data1.txt",
+            "--result-format",
+            "json",
+            "--max-line-passes",
+            "5",
+            "--checkpoint",
+            &state_path,
+        ]);
+        assert_eq!(status, Some(ExitStatus::Timeout));
+
+        let status = run(&[
+            "nonogram_solver",
+            "solve",
+            "--resume",
+            &state_path,
+            "--result-format",
+            "json",
+        ]);
+        assert_eq!(status, None);
+        std::fs::remove_file(&state_path).unwrap();
+    }
+
+    #[test]
+    fn test_solve_exit_status() {
+        let path = std::env::temp_dir().join(format!(
+            "nonogram_solver_stalled_{}.txt",
+            std::process::id()
+        ));
+        // 해가 두 개라서 라인 논리로는 멈춘다
+        std::fs::write(&path, "2 2\n1\n1\n1\n1\n").unwrap();
+        let path = path.display().to_string();
+
+        let Ok(Cli {
+            command: Command::Solve(args),
+        }) = parse_args(&["nonogram_solver", &path, "--result-format", "json"])
+        else {
+            panic!("Expected the solve command");
+        };
+        assert_eq!(
+            solve(&args).err().map(|e| e.status),
+            Some(ExitStatus::Stalled)
+        );
+
+        let Ok(Cli {
+            command: Command::Solve(args),
+        }) = parse_args(&[
+            "nonogram_solver",
+            &path,
+            "--engine",
+            "sat",
+            "--output-format",
+            "json",
+        ])
+        else {
+            panic!("Expected the solve command");
+        };
+        assert_eq!(solve(&args).err().map(|e| e.status), None);
+
+        // SAT 엔진이 쓰지 않는 옵션은 거절한다
+        let Ok(Cli {
+            command: Command::Solve(args),
+        }) = parse_args(&[
+            "nonogram_solver",
+            &path,
+            "--engine",
+            "sat",
+            "--heuristic",
+            "round-robin",
+        ])
+        else {
+            panic!("Expected the solve command");
+        };
+        let error = solve(&args).unwrap_err();
+        assert_eq!(error.status, ExitStatus::Error);
+        assert_eq!(
+            error.message.as_deref(),
+            Some("--heuristic only works with --engine line")
+        );
+
+        let Ok(Cli {
+            command: Command::Solve(args),
+        }) = parse_args(&[
+            "nonogram_solver",
+            &path,
+            "--level",
+            "search",
+            "--result-format",
+            "json",
+        ])
+        else {
+            panic!("Expected the solve command");
+        };
+        assert_eq!(solve(&args).err().map(|e| e.status), None);
+
+        let Ok(Cli {
+            command: Command::Solve(args),
+        }) = parse_args(&["nonogram_solver", "./sample/no_such_file.txt"])
+        else {
+            panic!("Expected the solve command");
+        };
+        assert_eq!(
+            solve(&args).err().map(|e| e.status),
+            Some(ExitStatus::ParseError)
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_event_log_and_replay() {
+        let log_path = std::env::temp_dir()
+            .join(format!(
+                "nonogram_solver_events_{}.jsonl",
+                std::process::id()
+            ))
+            .display()
+            .to_string();
+        let Ok(Cli {
+            command: Command::Solve(args),
+        }) = parse_args(&[
+            "nonogram_solver",
+            "sample/data1.txt",
+            "--event-log",
+            &log_path,
+            "--result-format",
+            "json",
+        ])
+        else {
+            panic!("Expected the solve command");
+        };
+        assert!(solve(&args).is_ok());
+
+        let Ok(Cli {
+            command: Command::Replay { log_path, speed },
+        }) = parse_args(&["nonogram_solver", "replay", &log_path, "--speed", "0"])
+        else {
+            panic!("Expected the replay command");
+        };
+        assert!(replay(&log_path, speed).is_ok());
+        assert!(replay(&log_path, -1.0).is_err());
+        std::fs::remove_file(&log_path).unwrap();
+    }
+
+    #[test]
+    fn test_solutions() {
+        let path = std::env::temp_dir().join(format!(
+            "nonogram_solver_ambiguous_{}.txt",
+            std::process::id()
+        ));
+        std::fs::write(&path, "2 2\n1\n1\n1\n1\n").unwrap();
+        let path = path.display().to_string();
+
+        let Ok(Cli {
+            command:
+                Command::Solutions {
+                    input,
+                    limit,
+                    limits,
+                    output,
+                },
+        }) = parse_args(&[
+            "nonogram_solver",
+            "solutions",
+            &path,
+            "--limit",
+            "3",
+            "--output-format",
+            "json",
+        ])
+        else {
+            panic!("Expected the solutions command");
+        };
+        assert_eq!(limit, 3);
+        assert!(solutions(&input, limit, &limits, &output).is_ok());
+
+        assert!(parse_args(&["nonogram_solver", "solutions", &path, "--limit", "0"]).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
     #[test]
     fn test_solve_normal_1() {
         assert!(solve_normal("./sample/data1.txt").is_ok());
@@ -0,0 +1,300 @@
+pub mod tui;
+
+use crate::board::{Board, Vec2};
+use crate::display::NullDisplay;
+use crate::generator::line_hint;
+use crate::solver::error::SolverError;
+use crate::solver::types::{Line, LineDirection};
+use crate::solver::{Cell, Solver};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct CellChange {
+    position: Vec2,
+    old: Cell,
+    new: Cell,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Hint {
+    /// The player marked this cell differently from the solution.
+    Mistake(Vec2),
+    /// This cell can be deduced from the current board.
+    Deduction(Vec2, Cell),
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CheckResult {
+    pub mistakes: Vec<Vec2>,
+    pub unknown_count: usize,
+    pub is_solved: bool,
+}
+
+/// State of a puzzle being played by hand.
+pub struct PlayState {
+    board: Board<Cell>,
+    row_hints: Vec<Vec<usize>>,
+    column_hints: Vec<Vec<usize>>,
+    solution: Board<Cell>,
+    cursor: Vec2,
+    undo_stack: Vec<CellChange>,
+    redo_stack: Vec<CellChange>,
+}
+
+impl PlayState {
+    pub fn new(
+        size: Vec2,
+        row_hints: Vec<Vec<usize>>,
+        column_hints: Vec<Vec<usize>>,
+    ) -> Result<Self, SolverError> {
+        let mut solver = Self::create_solver(size, &row_hints, &column_hints)?;
+        solver.solve()?;
+
+        Ok(Self {
+            board: Board::new(size, Cell::Unknown),
+            row_hints,
+            column_hints,
+            solution: solver.board().clone(),
+            cursor: Vec2::new(0, 0),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+        })
+    }
+
+    fn create_solver(
+        size: Vec2,
+        row_hints: &[Vec<usize>],
+        column_hints: &[Vec<usize>],
+    ) -> Result<Solver, SolverError> {
+        Solver::new(
+            size,
+            row_hints.to_vec(),
+            column_hints.to_vec(),
+            Box::new(NullDisplay),
+        )
+    }
+
+    pub fn board(&self) -> &Board<Cell> {
+        &self.board
+    }
+
+    pub fn cursor(&self) -> Vec2 {
+        self.cursor
+    }
+
+    pub fn line_hint(&self, line: Line) -> &[usize] {
+        match line.direction() {
+            LineDirection::Row => &self.row_hints[line.index()],
+            LineDirection::Column => &self.column_hints[line.index()],
+        }
+    }
+
+    pub fn move_cursor(&mut self, row_delta: isize, column_delta: isize) {
+        let size = self.board.size();
+        self.cursor = Vec2::new(
+            self.cursor
+                .row
+                .saturating_add_signed(row_delta)
+                .min(size.row - 1),
+            self.cursor
+                .column
+                .saturating_add_signed(column_delta)
+                .min(size.column - 1),
+        );
+    }
+
+    pub fn move_cursor_to(&mut self, position: Vec2) {
+        self.cursor = position;
+    }
+
+    /// Marks the cell under the cursor. Marking a cell with its current value clears it.
+    pub fn toggle_cell(&mut self, cell: Cell) {
+        let new = if *self.board.value(self.cursor) == cell {
+            Cell::Unknown
+        } else {
+            cell
+        };
+        self.set_cell(self.cursor, new);
+    }
+
+    pub fn set_cell(&mut self, position: Vec2, cell: Cell) {
+        let old = *self.board.value(position);
+        if old == cell {
+            return;
+        }
+
+        *self.board.value_mut(position) = cell;
+        self.undo_stack.push(CellChange {
+            position,
+            old,
+            new: cell,
+        });
+        self.redo_stack.clear();
+    }
+
+    pub fn undo(&mut self) -> bool {
+        let Some(change) = self.undo_stack.pop() else {
+            return false;
+        };
+        *self.board.value_mut(change.position) = change.old;
+        self.cursor = change.position;
+        self.redo_stack.push(change);
+        true
+    }
+
+    pub fn redo(&mut self) -> bool {
+        let Some(change) = self.redo_stack.pop() else {
+            return false;
+        };
+        *self.board.value_mut(change.position) = change.new;
+        self.cursor = change.position;
+        self.undo_stack.push(change);
+        true
+    }
+
+    /// Whether the filled cells of the line form exactly the runs of its hint.
+    pub fn is_line_satisfied(&self, line: Line) -> bool {
+        let cells: Box<dyn Iterator<Item = &Cell>> = match line.direction() {
+            LineDirection::Row => Box::new(self.board.iter_row(line.index())),
+            LineDirection::Column => Box::new(self.board.iter_column(line.index())),
+        };
+
+        let expected = self
+            .line_hint(line)
+            .iter()
+            .copied()
+            .filter(|&hint| hint > 0);
+        line_hint(cells).into_iter().eq(expected)
+    }
+
+    fn mistakes(&self) -> impl Iterator<Item = Vec2> + '_ {
+        let size = self.board.size();
+        (0..size.row)
+            .flat_map(move |row| (0..size.column).map(move |column| Vec2::new(row, column)))
+            .filter(|&position| {
+                let cell = *self.board.value(position);
+                let answer = *self.solution.value(position);
+                cell != Cell::Unknown && answer != Cell::Unknown && cell != answer
+            })
+    }
+
+    pub fn check(&self) -> CheckResult {
+        let size = self.board.size();
+        let is_solved = (0..size.row)
+            .map(|index| Line::new(LineDirection::Row, index))
+            .chain((0..size.column).map(|index| Line::new(LineDirection::Column, index)))
+            .all(|line| self.is_line_satisfied(line));
+
+        CheckResult {
+            mistakes: self.mistakes().collect(),
+            unknown_count: self
+                .board
+                .iter_all()
+                .filter(|&&cell| cell == Cell::Unknown)
+                .count(),
+            is_solved,
+        }
+    }
+
+    /// Asks the solver for the next cell that follows from the correct part of the board.
+    pub fn hint(&self) -> Result<Option<Hint>, SolverError> {
+        if let Some(position) = self.mistakes().next() {
+            return Ok(Some(Hint::Mistake(position)));
+        }
+
+        let size = self.board.size();
+        let mut solver = Self::create_solver(size, &self.row_hints, &self.column_hints)?;
+        // 해답이 정해진 칸만 넘겨서 추측한 칸으로부터 추론하지 않게 한다
+        for row in 0..size.row {
+            for column in 0..size.column {
+                let position = Vec2::new(row, column);
+                if *self.solution.value(position) != Cell::Unknown {
+                    solver.set_cell(position, *self.board.value(position));
+                }
+            }
+        }
+        let seeded = solver.board().clone();
+
+        while solver.solve_step()?.is_some() {
+            for row in 0..size.row {
+                for column in 0..size.column {
+                    let position = Vec2::new(row, column);
+                    let cell = *solver.board().value(position);
+                    if *seeded.value(position) == Cell::Unknown && cell != Cell::Unknown {
+                        return Ok(Some(Hint::Deduction(position, cell)));
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // ██  ██
+    //   ██
+    // ██  ██
+    fn create_state() -> PlayState {
+        PlayState::new(
+            Vec2::new(3, 3),
+            vec![vec![1, 1], vec![1], vec![1, 1]],
+            vec![vec![1, 1], vec![1], vec![1, 1]],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut state = create_state();
+        state.set_cell(Vec2::new(0, 0), Cell::Block);
+        state.set_cell(Vec2::new(0, 1), Cell::Blank);
+
+        assert!(state.undo());
+        assert_eq!(*state.board().value(Vec2::new(0, 1)), Cell::Unknown);
+        assert!(state.redo());
+        assert_eq!(*state.board().value(Vec2::new(0, 1)), Cell::Blank);
+
+        assert!(state.undo());
+        state.set_cell(Vec2::new(2, 2), Cell::Block);
+        assert!(!state.redo());
+    }
+
+    #[test]
+    fn test_line_satisfied() {
+        let mut state = create_state();
+        let row = Line::new(LineDirection::Row, 0);
+        state.set_cell(Vec2::new(0, 0), Cell::Block);
+        assert!(!state.is_line_satisfied(row));
+        state.set_cell(Vec2::new(0, 2), Cell::Block);
+        assert!(state.is_line_satisfied(row));
+        state.set_cell(Vec2::new(0, 1), Cell::Block);
+        assert!(!state.is_line_satisfied(row));
+    }
+
+    #[test]
+    fn test_hint() {
+        let mut state = create_state();
+        state.set_cell(Vec2::new(0, 1), Cell::Block);
+        assert_eq!(state.hint().unwrap(), Some(Hint::Mistake(Vec2::new(0, 1))));
+
+        state.undo();
+        let Some(Hint::Deduction(position, cell)) = state.hint().unwrap() else {
+            panic!("Expected a deduction");
+        };
+        assert_eq!(*state.solution.value(position), cell);
+    }
+
+    #[test]
+    fn test_check() {
+        let mut state = create_state();
+        for (row, column) in [(0, 0), (0, 2), (1, 1), (2, 0), (2, 2)] {
+            state.set_cell(Vec2::new(row, column), Cell::Block);
+        }
+        let result = state.check();
+        assert!(result.is_solved);
+        assert!(result.mistakes.is_empty());
+    }
+}
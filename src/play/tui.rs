@@ -0,0 +1,216 @@
+use std::io::{self, Write};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor},
+    terminal::{self, ClearType},
+};
+
+use super::{Hint, PlayState};
+use crate::board::Vec2;
+use crate::solver::types::{Line, LineDirection};
+use crate::solver::Cell;
+
+const HELP: &str =
+    "arrows/hjkl: move  space/f: fill  x: cross  c: clear  u: undo  r: redo  ?: hint  enter: check  q: quit";
+
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Runs the full-screen play mode until the player quits.
+pub fn run(state: &mut PlayState) -> io::Result<()> {
+    let _guard = TerminalGuard::enter()?;
+    let mut stdout = io::stdout();
+    let mut message = String::new();
+
+    loop {
+        draw(&mut stdout, state, &message)?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        if is_quit(key) {
+            return Ok(());
+        }
+        message = handle_key(state, key);
+    }
+}
+
+fn is_quit(key: KeyEvent) -> bool {
+    matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+        || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL))
+}
+
+fn handle_key(state: &mut PlayState, key: KeyEvent) -> String {
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => state.move_cursor(-1, 0),
+        KeyCode::Down | KeyCode::Char('j') => state.move_cursor(1, 0),
+        KeyCode::Left | KeyCode::Char('h') => state.move_cursor(0, -1),
+        KeyCode::Right | KeyCode::Char('l') => state.move_cursor(0, 1),
+        KeyCode::Char(' ') | KeyCode::Char('f') => state.toggle_cell(Cell::Block),
+        KeyCode::Char('x') => state.toggle_cell(Cell::Blank),
+        KeyCode::Char('c') | KeyCode::Backspace | KeyCode::Delete => {
+            state.set_cell(state.cursor(), Cell::Unknown)
+        }
+        KeyCode::Char('u') if !state.undo() => return "Nothing to undo.".to_string(),
+        KeyCode::Char('r') if !state.redo() => return "Nothing to redo.".to_string(),
+        KeyCode::Char('?') => return describe_hint(state),
+        KeyCode::Enter => {
+            let result = state.check();
+            return if result.is_solved {
+                "Solved!".to_string()
+            } else if !result.mistakes.is_empty() {
+                format!("{} mistake(s) found.", result.mistakes.len())
+            } else {
+                format!("No mistakes so far, {} cell(s) left.", result.unknown_count)
+            };
+        }
+        _ => {}
+    }
+    String::new()
+}
+
+fn describe_hint(state: &mut PlayState) -> String {
+    match state.hint() {
+        Ok(Some(Hint::Mistake(position))) => {
+            state.move_cursor_to(position);
+            format!(
+                "Row {}, column {} is wrong.",
+                position.row + 1,
+                position.column + 1
+            )
+        }
+        Ok(Some(Hint::Deduction(position, cell))) => {
+            state.move_cursor_to(position);
+            format!(
+                "Row {}, column {} must be {}.",
+                position.row + 1,
+                position.column + 1,
+                if cell == Cell::Block {
+                    "filled"
+                } else {
+                    "empty"
+                }
+            )
+        }
+        Ok(None) => "No cell can be deduced by line logic.".to_string(),
+        Err(e) => format!("Failed to find hint: {}", e),
+    }
+}
+
+fn format_hint(hint: &[usize]) -> String {
+    hint.iter()
+        .map(|number| number.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn clue_color(state: &PlayState, line: Line, is_active: bool) -> Color {
+    if state.is_line_satisfied(line) {
+        Color::DarkGrey
+    } else if is_active {
+        Color::Yellow
+    } else {
+        Color::Reset
+    }
+}
+
+fn draw<W: Write>(out: &mut W, state: &PlayState, message: &str) -> io::Result<()> {
+    let size = state.board().size();
+    let cursor = state.cursor();
+
+    let row_hints = (0..size.row)
+        .map(|row| format_hint(state.line_hint(Line::new(LineDirection::Row, row))))
+        .collect::<Vec<_>>();
+    let row_hint_width = row_hints.iter().map(String::len).max().unwrap_or(0);
+    let column_hint_height = (0..size.column)
+        .map(|column| {
+            state
+                .line_hint(Line::new(LineDirection::Column, column))
+                .len()
+        })
+        .max()
+        .unwrap_or(0);
+
+    queue!(out, cursor::MoveTo(0, 0), terminal::Clear(ClearType::All))?;
+
+    for level in 0..column_hint_height {
+        queue!(out, Print(" ".repeat(row_hint_width + 1)))?;
+        for column in 0..size.column {
+            let line = Line::new(LineDirection::Column, column);
+            let hint = state.line_hint(line);
+            // 힌트를 아래쪽으로 정렬
+            let text = match (level + hint.len()).checked_sub(column_hint_height) {
+                Some(index) => format!("{:>2}", hint[index]),
+                None => "  ".to_string(),
+            };
+            queue!(
+                out,
+                SetForegroundColor(clue_color(state, line, column == cursor.column)),
+                Print(text),
+                ResetColor
+            )?;
+        }
+        queue!(out, Print("\r\n"))?;
+    }
+
+    for (row, hint) in row_hints.iter().enumerate() {
+        let line = Line::new(LineDirection::Row, row);
+        queue!(
+            out,
+            SetForegroundColor(clue_color(state, line, row == cursor.row)),
+            Print(format!("{:>width$} ", hint, width = row_hint_width)),
+            ResetColor
+        )?;
+
+        for column in 0..size.column {
+            let position = Vec2::new(row, column);
+            let text = match state.board().value(position) {
+                Cell::Block => "██",
+                Cell::Blank => " ×",
+                _ => " ·",
+            };
+            if position == cursor {
+                queue!(
+                    out,
+                    SetAttribute(Attribute::Reverse),
+                    Print(text),
+                    SetAttribute(Attribute::Reset)
+                )?;
+            } else {
+                queue!(out, Print(text))?;
+            }
+        }
+        queue!(out, Print("\r\n"))?;
+    }
+
+    queue!(
+        out,
+        Print("\r\n"),
+        Print(message),
+        Print("\r\n"),
+        SetForegroundColor(Color::DarkGrey),
+        Print(HELP),
+        ResetColor
+    )?;
+    out.flush()
+}
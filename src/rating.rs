@@ -0,0 +1,104 @@
+use std::fmt::Display;
+
+use crate::display::NullDisplay;
+use crate::solver::error::SolverError;
+use crate::solver::limits::SolveLimits;
+use crate::solver::parser::SolverParseResult;
+use crate::solver::Solver;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+    /// Line logic alone leaves unknown cells.
+    BeyondLineLogic,
+}
+
+impl Difficulty {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+            Difficulty::BeyondLineLogic => "beyond line logic",
+        }
+    }
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Debug)]
+pub struct Rating {
+    pub difficulty: Difficulty,
+    pub line_passes: usize,
+    pub placements: usize,
+    /// Line passes divided by the number of lines.
+    pub passes_per_line: f64,
+    pub solved: bool,
+}
+
+/// Rates a puzzle by how much line logic it needs: the more often each line has to be
+/// revisited, the harder the puzzle.
+pub fn rate(puzzle: &SolverParseResult, limits: SolveLimits) -> Result<Rating, SolverError> {
+    let mut solver = Solver::new(
+        puzzle.board_size,
+        puzzle.row_hints.clone(),
+        puzzle.column_hints.clone(),
+        Box::new(NullDisplay),
+    )?;
+    solver.set_limits(limits);
+    solver.solve()?;
+
+    let line_count = puzzle.board_size.row + puzzle.board_size.column;
+    let passes_per_line = solver.line_passes() as f64 / line_count as f64;
+    let solved = solver.is_solved();
+    let difficulty = if !solved {
+        Difficulty::BeyondLineLogic
+    } else if passes_per_line <= 1.5 {
+        Difficulty::Easy
+    } else if passes_per_line <= 3.0 {
+        Difficulty::Medium
+    } else {
+        Difficulty::Hard
+    };
+
+    Ok(Rating {
+        difficulty,
+        line_passes: solver.line_passes(),
+        placements: solver.placements(),
+        passes_per_line,
+        solved,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Vec2;
+
+    #[test]
+    fn test_rate() {
+        let puzzle = SolverParseResult {
+            board_size: Vec2::new(2, 2),
+            row_hints: vec![vec![2], vec![1]],
+            column_hints: vec![vec![2], vec![1]],
+        };
+        let rating = rate(&puzzle, SolveLimits::default()).unwrap();
+        assert!(rating.solved);
+        assert_eq!(rating.difficulty, Difficulty::Easy);
+
+        // 두 가지 해가 있어서 라인 논리로 풀 수 없다
+        let puzzle = SolverParseResult {
+            board_size: Vec2::new(2, 2),
+            row_hints: vec![vec![1], vec![1]],
+            column_hints: vec![vec![1], vec![1]],
+        };
+        let rating = rate(&puzzle, SolveLimits::default()).unwrap();
+        assert_eq!(rating.difficulty, Difficulty::BeyondLineLogic);
+    }
+}
@@ -0,0 +1,364 @@
+use std::io::{self, Read};
+use std::net::SocketAddr;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Server};
+
+use crate::board::{Board, Vec2};
+use crate::display::NullDisplay;
+use crate::generator::{derive_hints, find_wrong_lines, generate_board, Random};
+use crate::solver::error::SolverError;
+use crate::solver::limits::SolveLimits;
+use crate::solver::parser::{AutoSolverParser, SolverParseResult, SolverParser};
+use crate::solver::Solver;
+
+const MAX_GENERATE_SIZE: usize = 100;
+/// Largest number of rows or columns of a puzzle sent to /solve or /verify.
+const MAX_PUZZLE_SIZE: usize = 1000;
+
+pub struct ServerConfig {
+    pub port: u16,
+    pub max_body_size: usize,
+    pub solve_timeout: Duration,
+    /// Requests handled at the same time. Others wait until a worker is free.
+    pub workers: usize,
+}
+
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "status": "error", "error": message.into() }),
+        }
+    }
+}
+
+/// Serves the solver on `127.0.0.1:<port>` until the process is stopped. `on_listen` is called
+/// with the bound address once requests can be accepted.
+pub fn serve(config: ServerConfig, on_listen: impl FnOnce(SocketAddr)) -> io::Result<()> {
+    let server = Server::http(("127.0.0.1", config.port)).map_err(io::Error::other)?;
+    if let Some(address) = server.server_addr().to_ip() {
+        on_listen(address);
+    }
+
+    thread::scope(|scope| {
+        for _ in 0..config.workers.max(1) {
+            scope.spawn(|| {
+                for request in server.incoming_requests() {
+                    respond(request, &config);
+                }
+            });
+        }
+    });
+
+    Ok(())
+}
+
+fn respond(mut request: Request, config: &ServerConfig) {
+    let response = match read_body(&mut request, config.max_body_size) {
+        Ok(body) => handle_request(request.method(), request.url(), &body, config),
+        Err(response) => response,
+    };
+
+    let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+    let _ = request.respond(
+        tiny_http::Response::from_string(response.body.to_string())
+            .with_status_code(response.status)
+            .with_header(content_type),
+    );
+}
+
+fn read_body(request: &mut Request, max_body_size: usize) -> Result<String, Response> {
+    let too_large = || Response::error(413, format!("Body is larger than {} bytes", max_body_size));
+
+    if request
+        .body_length()
+        .is_some_and(|length| length > max_body_size)
+    {
+        return Err(too_large());
+    }
+
+    let mut body = Vec::new();
+    request
+        .as_reader()
+        .take(max_body_size as u64 + 1)
+        .read_to_end(&mut body)
+        .map_err(|e| Response::error(400, format!("Failed to read body: {}", e)))?;
+    if body.len() > max_body_size {
+        return Err(too_large());
+    }
+
+    String::from_utf8(body).map_err(|_| Response::error(400, "Body is not valid UTF-8"))
+}
+
+pub fn handle_request(method: &Method, url: &str, body: &str, config: &ServerConfig) -> Response {
+    let path = url.split('?').next().unwrap_or_default();
+    match (method, path) {
+        (Method::Post, "/solve") => solve(body, config.solve_timeout),
+        (Method::Post, "/verify") => verify(body),
+        (Method::Post, "/generate") => generate(body, config.solve_timeout),
+        (_, "/solve" | "/verify" | "/generate") => Response::error(405, "Method not allowed"),
+        _ => Response::error(404, "Not found"),
+    }
+}
+
+/// Solves the puzzle, stopping at the deadline with whatever was deduced so far.
+fn solve(puzzle: &str, timeout: Duration) -> Response {
+    let puzzle = match parse_puzzle(puzzle) {
+        Ok(puzzle) => puzzle,
+        Err(response) => return response,
+    };
+    let mut solver = match Solver::new(
+        puzzle.board_size,
+        puzzle.row_hints,
+        puzzle.column_hints,
+        Box::new(NullDisplay),
+    ) {
+        Ok(solver) => solver,
+        Err(e) => return solver_error(&e),
+    };
+    solver.set_limits(SolveLimits {
+        deadline: Some(Instant::now() + timeout),
+        ..Default::default()
+    });
+
+    let (status, code, board) = match solver.solve() {
+        Ok(()) if solver.is_solved() => ("solved", 200, solver.board().clone()),
+        Ok(()) => ("stalled", 200, solver.board().clone()),
+        Err(SolverError::Aborted(e)) => ("timeout", 504, e.board),
+        Err(SolverError::Contradiction(_)) => ("contradiction", 200, solver.board().clone()),
+        Err(e) => return solver_error(&e),
+    };
+
+    Response {
+        status: code,
+        body: json!({
+            "status": status,
+            "rows": board.size().row,
+            "columns": board.size().column,
+            "board": board.to_rows(),
+        }),
+    }
+}
+
+/// 400 for hints that cannot make a puzzle, 500 for anything that went wrong while solving.
+fn solver_error(e: &SolverError) -> Response {
+    let status = match e {
+        SolverError::InvalidBoardSize(..) | SolverError::InvalidInitialInfo(_) => 400,
+        _ => 500,
+    };
+    Response::error(status, e.to_string())
+}
+
+/// Parses a puzzle in any format, rejecting sizes above [`MAX_PUZZLE_SIZE`] before anything
+/// the size of the board is allocated.
+fn parse_puzzle(puzzle: &str) -> Result<SolverParseResult, Response> {
+    let puzzle = AutoSolverParser::new(puzzle)
+        .parse()
+        .map_err(|e| Response::error(400, e))?;
+    let size = puzzle.board_size;
+    if size.row > MAX_PUZZLE_SIZE || size.column > MAX_PUZZLE_SIZE {
+        return Err(Response::error(
+            400,
+            format!("Size must be at most {}", MAX_PUZZLE_SIZE),
+        ));
+    }
+    Ok(puzzle)
+}
+
+/// Checks a proposed board against the hints of a puzzle.
+///
+/// The body is `{"puzzle": "<puzzle in any format>", "board": ["#..#", ...]}`.
+fn verify(body: &str) -> Response {
+    let request: Value = match serde_json::from_str(body) {
+        Ok(value) => value,
+        Err(e) => return Response::error(400, format!("Invalid JSON: {}", e)),
+    };
+    let Some(puzzle) = request["puzzle"].as_str() else {
+        return Response::error(400, "Missing \"puzzle\"");
+    };
+    let Some(rows) = request["board"].as_array() else {
+        return Response::error(400, "Missing \"board\"");
+    };
+
+    let Some(rows) = rows.iter().map(Value::as_str).collect::<Option<Vec<_>>>() else {
+        return Response::error(400, "Rows of \"board\" must be strings");
+    };
+
+    let puzzle = match parse_puzzle(puzzle) {
+        Ok(puzzle) => puzzle,
+        Err(response) => return response,
+    };
+    let board = match Board::from_rows(puzzle.board_size, &rows) {
+        Ok(board) => board,
+        Err(e) => return Response::error(400, e),
+    };
+    let (wrong_rows, wrong_columns) = find_wrong_lines(&puzzle, &board);
+
+    Response::ok(json!({
+        "status": if wrong_rows.is_empty() && wrong_columns.is_empty() { "correct" } else { "incorrect" },
+        "wrong_rows": wrong_rows,
+        "wrong_columns": wrong_columns,
+    }))
+}
+
+/// Generates a random puzzle.
+///
+/// The body is `{"rows": 10, "columns": 10, "density": 0.5, "seed": 1}`; every field is optional.
+fn generate(body: &str, timeout: Duration) -> Response {
+    let request: Value = if body.trim().is_empty() {
+        json!({})
+    } else {
+        match serde_json::from_str(body) {
+            Ok(value) => value,
+            Err(e) => return Response::error(400, format!("Invalid JSON: {}", e)),
+        }
+    };
+
+    let rows = request["rows"].as_u64().unwrap_or(10) as usize;
+    let columns = request["columns"].as_u64().unwrap_or(10) as usize;
+    let density = request["density"].as_f64().unwrap_or(0.5);
+    let seed = request["seed"].as_u64().unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos() as u64)
+            .unwrap_or_default()
+    });
+
+    if !(1..=MAX_GENERATE_SIZE).contains(&rows) || !(1..=MAX_GENERATE_SIZE).contains(&columns) {
+        return Response::error(
+            400,
+            format!("Size must be between 1 and {}", MAX_GENERATE_SIZE),
+        );
+    }
+    if !(0.0..=1.0).contains(&density) {
+        return Response::error(400, "Density must be between 0 and 1");
+    }
+
+    let board = generate_board(Vec2::new(rows, columns), density, &mut Random::new(seed));
+    let puzzle = derive_hints(&board).to_text();
+
+    let solved = solve(&puzzle, timeout);
+    let line_solvable = solved.body["status"] == "solved";
+
+    Response::ok(json!({
+        "seed": seed,
+        "puzzle": puzzle,
+        "solution": board.to_rows(),
+        "line_solvable": line_solvable,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ServerConfig {
+        ServerConfig {
+            port: 0,
+            max_body_size: 1 << 20,
+            solve_timeout: Duration::from_secs(30),
+            workers: 1,
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        let puzzle = include_str!("../sample/data1.txt");
+        let response = handle_request(&Method::Post, "/solve", puzzle, &config());
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body["status"], "solved");
+        assert_eq!(response.body["board"].as_array().unwrap().len(), 10);
+    }
+
+    #[test]
+    fn test_solve_timeout() {
+        let puzzle = include_str!("../sample/data1.txt");
+        let config = ServerConfig {
+            solve_timeout: Duration::ZERO,
+            ..config()
+        };
+        let response = handle_request(&Method::Post, "/solve", puzzle, &config);
+        assert_eq!(response.status, 504);
+        assert_eq!(response.body["status"], "timeout");
+    }
+
+    #[test]
+    fn test_solve_invalid() {
+        let response = handle_request(&Method::Post, "/solve", "not a puzzle", &config());
+        assert_eq!(response.status, 400);
+    }
+
+    #[test]
+    fn test_solver_error_status() {
+        assert_eq!(
+            solver_error(&SolverError::InvalidBoardSize(0, 0)).status,
+            400
+        );
+        assert_eq!(
+            solver_error(&SolverError::Checkpoint("disk full".to_string())).status,
+            500
+        );
+    }
+
+    #[test]
+    fn test_too_large() {
+        // 힌트가 모두 빈 줄이어도 파싱은 된다
+        let puzzle = format!(
+            "{} 1\n{}",
+            MAX_PUZZLE_SIZE + 1,
+            "\n".repeat(MAX_PUZZLE_SIZE + 2)
+        );
+        let response = handle_request(&Method::Post, "/solve", &puzzle, &config());
+        assert_eq!(response.status, 400);
+        assert!(response.body["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("Size must be"));
+        let request = json!({ "puzzle": puzzle, "board": [] });
+        let response = handle_request(&Method::Post, "/verify", &request.to_string(), &config());
+        assert_eq!(response.status, 400);
+    }
+
+    #[test]
+    fn test_generate_and_verify() {
+        let response = handle_request(
+            &Method::Post,
+            "/generate",
+            r#"{"rows": 6, "columns": 8, "seed": 7}"#,
+            &config(),
+        );
+        assert_eq!(response.status, 200);
+
+        let request = json!({
+            "puzzle": response.body["puzzle"],
+            "board": response.body["solution"],
+        });
+        let response = handle_request(&Method::Post, "/verify", &request.to_string(), &config());
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body["status"], "correct");
+    }
+
+    #[test]
+    fn test_unknown_route() {
+        assert_eq!(
+            handle_request(&Method::Get, "/solve", "", &config()).status,
+            405
+        );
+        assert_eq!(
+            handle_request(&Method::Post, "/", "", &config()).status,
+            404
+        );
+    }
+}
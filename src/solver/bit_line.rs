@@ -0,0 +1,284 @@
+use super::cell::Cell;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+fn word_count(len: usize) -> usize {
+    len.div_ceil(WORD_BITS)
+}
+
+/// Cells of one line as bit masks. `known` marks decided cells and `filled` marks the filled
+/// ones among them, so comparing lines takes a few word operations instead of one per cell.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BitLine {
+    len: usize,
+    filled: Vec<u64>,
+    known: Vec<u64>,
+}
+
+impl BitLine {
+    /// A line of unknown cells.
+    pub fn new(len: usize) -> Self {
+        Self {
+            len,
+            filled: vec![0; word_count(len)],
+            known: vec![0; word_count(len)],
+        }
+    }
+
+    /// A line of blank cells.
+    pub fn blank(len: usize) -> Self {
+        let mut line = Self::new(len);
+        line.set_all_known();
+        line
+    }
+
+    pub fn from_cells(cells: &[Cell]) -> Self {
+        let mut line = Self::new(cells.len());
+        for (index, &cell) in cells.iter().enumerate() {
+            line.set(index, cell);
+        }
+        line
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> Cell {
+        let (word, bit) = (index / WORD_BITS, 1 << (index % WORD_BITS));
+        match (self.known[word] & bit != 0, self.filled[word] & bit != 0) {
+            (false, _) => Cell::Unknown,
+            (true, true) => Cell::Block,
+            (true, false) => Cell::Blank,
+        }
+    }
+
+    /// Sets a cell. [`Cell::Crash`] is stored as unknown.
+    pub fn set(&mut self, index: usize, cell: Cell) {
+        let (word, bit) = (index / WORD_BITS, 1 << (index % WORD_BITS));
+        match cell {
+            Cell::Block => {
+                self.known[word] |= bit;
+                self.filled[word] |= bit;
+            }
+            Cell::Blank => {
+                self.known[word] |= bit;
+                self.filled[word] &= !bit;
+            }
+            Cell::Unknown | Cell::Crash => {
+                self.known[word] &= !bit;
+                self.filled[word] &= !bit;
+            }
+        }
+    }
+
+    pub fn to_cells(&self) -> Vec<Cell> {
+        (0..self.len).map(|index| self.get(index)).collect()
+    }
+
+    /// Mask of the bits that belong to the line in word `word`.
+    fn word_mask(&self, word: usize) -> u64 {
+        let rest = self.len - word * WORD_BITS;
+        if rest >= WORD_BITS {
+            u64::MAX
+        } else {
+            (1 << rest) - 1
+        }
+    }
+
+    fn set_all_known(&mut self) {
+        for word in 0..self.known.len() {
+            self.known[word] = self.word_mask(word);
+        }
+    }
+
+    pub fn known_count(&self) -> usize {
+        self.known
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn unknown_count(&self) -> usize {
+        self.len - self.known_count()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.known_count() == self.len
+    }
+
+    /// Fills `len` cells from `start`.
+    fn fill_range(&mut self, start: usize, len: usize) {
+        let end = start + len;
+        let mut index = start;
+        while index < end {
+            let word = index / WORD_BITS;
+            let offset = index % WORD_BITS;
+            let count = (WORD_BITS - offset).min(end - index);
+            let bits = if count == WORD_BITS {
+                u64::MAX
+            } else {
+                ((1 << count) - 1) << offset
+            };
+            self.filled[word] |= bits;
+            index += count;
+        }
+    }
+
+    /// Turns this line into the placement with blank runs `blanks` between the blocks `blocks`,
+    /// as returned by [`NumberDistributionCalculator::calc_distribute_number`].
+    ///
+    /// [`NumberDistributionCalculator::calc_distribute_number`]:
+    ///     super::calculator::NumberDistributionCalculator::calc_distribute_number
+    pub fn set_placement(&mut self, blanks: &[usize], blocks: &[usize]) {
+        self.filled.iter_mut().for_each(|word| *word = 0);
+        self.set_all_known();
+
+        let mut position = 0;
+        for (blank, &block) in blanks.iter().zip(blocks) {
+            position += blank;
+            self.fill_range(position, block);
+            position += block + 1;
+        }
+    }
+
+    /// Whether the fully known `placement` agrees with every known cell of this line.
+    pub fn fits(&self, placement: &BitLine) -> bool {
+        self.filled
+            .iter()
+            .zip(&self.known)
+            .zip(&placement.filled)
+            .all(|((filled, known), placement)| (filled ^ placement) & known == 0)
+    }
+
+    /// Cells known in `other` but not in `self`, as `(index, cell)` pairs.
+    pub fn newly_known<'a>(
+        &'a self,
+        other: &'a BitLine,
+    ) -> impl Iterator<Item = (usize, Cell)> + 'a {
+        self.known.iter().zip(&other.known).enumerate().flat_map(
+            move |(word, (known, other_known))| {
+                let mut bits = other_known & !known;
+                std::iter::from_fn(move || {
+                    if bits == 0 {
+                        return None;
+                    }
+                    let index = word * WORD_BITS + bits.trailing_zeros() as usize;
+                    bits &= bits - 1;
+                    Some((index, other.get(index)))
+                })
+            },
+        )
+    }
+}
+
+/// Cells shared by every placement added so far.
+pub(crate) struct PlacementIntersection {
+    len: usize,
+    always_filled: Vec<u64>,
+    ever_filled: Vec<u64>,
+    count: usize,
+}
+
+impl PlacementIntersection {
+    pub fn new(len: usize) -> Self {
+        Self {
+            len,
+            always_filled: vec![u64::MAX; word_count(len)],
+            ever_filled: vec![0; word_count(len)],
+            count: 0,
+        }
+    }
+
+    pub fn add(&mut self, placement: &BitLine) {
+        for (word, &filled) in placement.filled.iter().enumerate() {
+            self.always_filled[word] &= filled;
+            self.ever_filled[word] |= filled;
+        }
+        self.count += 1;
+    }
+
+    /// Whether every unknown cell of `current_line` already differs between two placements,
+    /// so that more placements cannot decide anything.
+    pub fn is_exhausted(&self, current_line: &BitLine) -> bool {
+        self.count > 0
+            && (0..self.always_filled.len()).all(|word| {
+                let undecided = self.ever_filled[word] & !self.always_filled[word];
+                !current_line.known[word] & !undecided & current_line.word_mask(word) == 0
+            })
+    }
+
+    /// Cells filled in every placement are filled, cells filled in none are blank.
+    pub fn to_line(&self) -> BitLine {
+        let mut line = BitLine::new(self.len);
+        if self.count == 0 {
+            return line;
+        }
+        for word in 0..line.known.len() {
+            let mask = line.word_mask(word);
+            line.filled[word] = self.always_filled[word] & mask;
+            line.known[word] = (self.always_filled[word] | !self.ever_filled[word]) & mask;
+        }
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Cell::*;
+
+    #[test]
+    fn test_cells_round_trip() {
+        let cells = (0..130)
+            .map(|index| [Block, Blank, Unknown][index % 3])
+            .collect::<Vec<_>>();
+        let line = BitLine::from_cells(&cells);
+        assert_eq!(line.to_cells(), cells);
+        assert_eq!(line.unknown_count(), 43);
+    }
+
+    #[test]
+    fn test_placement_fits() {
+        let mut placement = BitLine::new(70);
+        // 빈칸 2, 블록 3, 빈칸 1+60, 블록 2, 빈칸 1+0
+        placement.set_placement(&[2, 60, 0], &[3, 2]);
+        let cells = placement.to_cells();
+        assert_eq!(&cells[..6], &[Blank, Blank, Block, Block, Block, Blank]);
+        assert_eq!(&cells[66..], &[Block, Block, Blank, Blank]);
+
+        let mut current = BitLine::new(70);
+        current.set(3, Block);
+        current.set(67, Block);
+        assert!(current.fits(&placement));
+        current.set(69, Block);
+        assert!(!current.fits(&placement));
+    }
+
+    #[test]
+    fn test_intersection() {
+        let mut first = BitLine::new(5);
+        first.set_placement(&[0, 1], &[3]);
+        let mut second = BitLine::new(5);
+        second.set_placement(&[1, 0], &[3]);
+
+        let mut intersection = PlacementIntersection::new(5);
+        intersection.add(&first);
+        intersection.add(&second);
+        assert_eq!(
+            intersection.to_line().to_cells(),
+            vec![Unknown, Block, Block, Unknown, Blank]
+        );
+
+        let current = BitLine::new(5);
+        assert!(!intersection.is_exhausted(&current));
+        let newly_known = current
+            .newly_known(&intersection.to_line())
+            .collect::<Vec<_>>();
+        assert_eq!(newly_known, vec![(1, Block), (2, Block), (4, Blank)]);
+    }
+}
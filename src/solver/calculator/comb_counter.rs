@@ -3,6 +3,12 @@ pub struct CombCounter {
     cache: Vec<Vec<Option<T>>>,
 }
 
+impl Default for CombCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CombCounter {
     pub fn new() -> Self {
         Self {
@@ -22,18 +28,23 @@ impl CombCounter {
         let result = match count {
             ..1 => unreachable!(),
             1 => 1,
-            2 => amount + 1,
+            2 => amount.saturating_add(1),
+            // 너무 큰 값은 넘치지 않고 T::MAX에 머문다
             3.. => (0..=amount)
                 .rev()
                 .map(|x| self.calc_comb_count(x, count - 1))
-                .sum(),
+                .fold(0, T::saturating_add),
         };
 
-        if let Some(row) = self.cache.get_mut(amount) {
-            if let Some(cell) = row.get_mut(count) {
-                *cell = Some(result);
-            }
+        // 캐시에 없는 크기는 필요한 만큼 늘린다
+        if self.cache.len() <= amount {
+            self.cache.resize(amount + 1, Vec::new());
         }
+        let row = &mut self.cache[amount];
+        if row.len() <= count {
+            row.resize(count + 1, None);
+        }
+        row[count] = Some(result);
 
         result
     }
@@ -56,4 +67,18 @@ mod test {
         let result = comb_counter.calc_comb_count(10, 1);
         assert_eq!(result, 1);
     }
+
+    #[test]
+    fn test_calc_comb_count_beyond_initial_cache() {
+        let mut comb_counter = CombCounter::new();
+        // C(150 + 2, 2)
+        assert_eq!(comb_counter.calc_comb_count(150, 3), 11476);
+        assert_eq!(comb_counter.calc_comb_count(3, 120), 295240);
+    }
+
+    #[test]
+    fn test_calc_comb_count_saturates() {
+        let mut comb_counter = CombCounter::new();
+        assert_eq!(comb_counter.calc_comb_count(90, 60), usize::MAX);
+    }
 }
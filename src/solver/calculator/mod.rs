@@ -1,4 +1,5 @@
 mod comb_counter;
 mod number_distribution_calculator;
 
-pub(crate) use number_distribution_calculator::NumberDistributionCalculator;
+pub use comb_counter::CombCounter;
+pub use number_distribution_calculator::NumberDistributionCalculator;
@@ -1,3 +1,4 @@
+use crate::solver::bit_line::BitLine;
 use crate::solver::cell::Cell;
 
 use super::comb_counter;
@@ -7,6 +8,12 @@ pub struct NumberDistributionCalculator {
     result_cache: Vec<usize>,
 }
 
+impl Default for NumberDistributionCalculator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl NumberDistributionCalculator {
     pub fn new() -> Self {
         Self {
@@ -63,10 +70,17 @@ impl NumberDistributionCalculator {
         hint_numbers: &[usize],
         length: usize,
     ) -> usize {
-        self.comb_counter.calc_comb_count(
-            length + 1 - hint_numbers.iter().sum::<usize>() - hint_numbers.len(),
-            hint_numbers.len() + 1,
-        )
+        // 힌트가 줄에 들어가지 않으면 배치할 방법이 없다
+        let free_space = hint_numbers
+            .iter()
+            .try_fold(hint_numbers.len(), |acc, &n| acc.checked_add(n))
+            .and_then(|needed| (length + 1).checked_sub(needed));
+        match free_space {
+            Some(free_space) => self
+                .comb_counter
+                .calc_comb_count(free_space, hint_numbers.len() + 1),
+            None => 0,
+        }
     }
 
     pub fn calc_distribute_number_line_hint(
@@ -85,22 +99,41 @@ impl NumberDistributionCalculator {
         Ok(())
     }
 
+    /// Like [`Self::calc_distribute_number_line_hint`], but writes the placement as bit masks.
+    pub fn calc_placement_line(
+        &mut self,
+        hint_numbers: &[usize],
+        length: usize,
+        index: usize,
+        result: &mut BitLine,
+    ) -> Result<(), String> {
+        let distribute = self.calc_distribute_number(
+            length + 1 - hint_numbers.iter().sum::<usize>() - hint_numbers.len(),
+            hint_numbers.len() + 1,
+            index,
+        )?;
+        result.set_placement(distribute, hint_numbers);
+        Ok(())
+    }
+
     fn create_line(blanks: &[usize], blocks: &[usize], result: &mut Vec<Cell>) {
         result.clear();
         for i in 0..blocks.len() {
-            result.extend(std::iter::repeat(Cell::Blank).take(blanks[i]));
-            result.extend(std::iter::repeat(Cell::Block).take(blocks[i]));
+            result.extend(std::iter::repeat_n(Cell::Blank, blanks[i]));
+            result.extend(std::iter::repeat_n(Cell::Block, blocks[i]));
             if i < blanks.len() - 1 {
                 result.push(Cell::Blank);
             }
         }
-        result.extend(std::iter::repeat(Cell::Blank).take(blanks[blanks.len() - 1]));
+        result.extend(std::iter::repeat_n(Cell::Blank, blanks[blanks.len() - 1]));
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use proptest::prelude::*;
+    use std::collections::HashSet;
 
     pub fn calc_distribute_number_temp(
         amount: usize,
@@ -112,6 +145,17 @@ mod test {
         Ok(distribute_number.result_cache.clone())
     }
 
+    #[test]
+    fn test_count_line_hint_does_not_fit() {
+        let mut calculator = NumberDistributionCalculator::new();
+        assert_eq!(calculator.calc_distribute_count_line_hint(&[], 3), 1);
+        assert_eq!(calculator.calc_distribute_count_line_hint(&[2, 2], 4), 0);
+        assert_eq!(
+            calculator.calc_distribute_count_line_hint(&[usize::MAX, 1], 4),
+            0
+        );
+    }
+
     #[test]
     fn test_calc_distribute_number_1() {
         let result = calc_distribute_number_temp(5, 3, 0);
@@ -183,4 +227,48 @@ mod test {
         let result = calc_distribute_number_temp(5, 3, 50);
         assert!(result.is_err());
     }
+
+    fn binomial(n: usize, k: usize) -> usize {
+        (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+    }
+
+    proptest! {
+        #[test]
+        fn test_placement_line_matches_cells(
+            hint in prop::collection::vec(1usize..5, 1..4),
+            extra in 0usize..70,
+        ) {
+            let length = hint.iter().sum::<usize>() + hint.len() - 1 + extra;
+            let mut calculator = NumberDistributionCalculator::new();
+            let mut cells = Vec::new();
+            let mut placement = BitLine::new(length);
+            let total = calculator.calc_distribute_count_line_hint(&hint, length).min(200);
+            for index in 0..total {
+                calculator.calc_distribute_number_line_hint(&hint, length, index, &mut cells).unwrap();
+                calculator.calc_placement_line(&hint, length, index, &mut placement).unwrap();
+                // 셀 배열은 끝에 빈칸이 하나 더 붙는다
+                prop_assert_eq!(&placement.to_cells()[..], &cells[..length]);
+            }
+        }
+
+
+        #[test]
+        fn test_each_distribution_once(amount in 0usize..10, count in 2usize..7) {
+            let mut calculator = NumberDistributionCalculator::new();
+            let total = calculator.comb_counter.calc_comb_count(amount, count);
+            prop_assert_eq!(total, binomial(amount + count - 1, count - 1));
+
+            let mut seen = HashSet::new();
+            for index in 0..total {
+                let distribution = calculator
+                    .calc_distribute_number(amount, count, index)
+                    .unwrap()
+                    .to_vec();
+                prop_assert_eq!(distribution.len(), count);
+                prop_assert_eq!(distribution.iter().sum::<usize>(), amount);
+                prop_assert!(seen.insert(distribution));
+            }
+            prop_assert!(calculator.calc_distribute_number(amount, count, total).is_err());
+        }
+    }
 }
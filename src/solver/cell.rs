@@ -1,6 +1,8 @@
 use std::fmt::Display;
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+use crate::board::{Board, Vec2};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum Cell {
     Block = 0b10,
     Blank = 0b01,
@@ -37,6 +39,48 @@ impl Display for Cell {
     }
 }
 
+impl Board<Cell> {
+    /// One string per row, using `#` for filled, `.` for empty, `!` for crashed and `?` for unknown cells.
+    pub fn to_rows(&self) -> Vec<String> {
+        (0..self.size().row)
+            .map(|row| {
+                self.iter_row(row)
+                    .map(|cell| match cell {
+                        Cell::Block => '#',
+                        Cell::Blank => '.',
+                        Cell::Crash => '!',
+                        Cell::Unknown => '?',
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Reads a finished board written as one string per row. `#`, `1`, `X` and `█` are filled cells,
+    /// anything else is empty.
+    pub fn from_rows(size: Vec2, rows: &[&str]) -> Result<Self, String> {
+        if rows.len() != size.row {
+            return Err(format!("Board must have {} rows", size.row));
+        }
+
+        let mut board = Board::new(size, Cell::Blank);
+        for (row, text) in rows.iter().enumerate() {
+            if text.chars().count() != size.column {
+                return Err(format!(
+                    "Row {} must be a string of {} cells",
+                    row, size.column
+                ));
+            }
+            for (column, ch) in text.chars().enumerate() {
+                if matches!(ch, '#' | '1' | 'X' | 'x' | '█') {
+                    *board.value_mut(Vec2::new(row, column)) = Cell::Block;
+                }
+            }
+        }
+        Ok(board)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -71,4 +115,12 @@ mod test {
     fn test_bitor_6() {
         assert_eq!(Crash | Unknown, Crash);
     }
+
+    #[test]
+    fn test_rows_round_trip() {
+        let board = Board::from_rows(Vec2::new(2, 3), &["#.#", "..#"]).unwrap();
+        assert_eq!(board.to_rows(), vec!["#.#", "..#"]);
+        assert!(Board::from_rows(Vec2::new(2, 3), &["#.#"]).is_err());
+        assert!(Board::from_rows(Vec2::new(1, 3), &["#."]).is_err());
+    }
 }
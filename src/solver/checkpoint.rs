@@ -0,0 +1,322 @@
+use std::fs;
+use std::io::Write;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+
+use super::cell::Cell;
+use super::error::SolverError;
+use super::placements::PlacementSet;
+use super::scheduler::LineHeuristic;
+use super::solver_display::SolverDisplay;
+use super::Solver;
+use crate::board::Vec2;
+
+const CHECKPOINT_VERSION: u64 = 1;
+
+/// Where and how often [`Solver::solve`] saves its state.
+pub(crate) struct CheckpointSchedule {
+    path: PathBuf,
+    interval: Duration,
+    last_saved: Instant,
+}
+
+impl Solver {
+    /// Saves the state to `path` every `interval` while solving, and once more if solving is
+    /// aborted. Boards that depend on a guess of [`SolveLevel`](super::probing::SolveLevel) are
+    /// not saved. The file is replaced atomically, so a killed process leaves the last full state.
+    pub fn set_checkpoint(&mut self, path: impl Into<PathBuf>, interval: Duration) {
+        self.checkpoint_schedule = Some(CheckpointSchedule {
+            path: path.into(),
+            interval,
+            last_saved: Instant::now(),
+        });
+    }
+
+    /// Everything needed to continue solving: hints, cells, surviving placements, the queue of
+    /// lines to solve and the work done so far. The line cache is not saved.
+    pub fn save_state(&self) -> Value {
+        let size = self.board.size();
+        let possibilities = self
+            .possibilities
+            .iter()
+            .map(|set| {
+                set.ranges()
+                    .iter()
+                    .map(|range| [range.start, range.end])
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let queue = self
+            .line_queue
+            .entries()
+            .into_iter()
+            .map(|(cost, index)| json!([index, cost, self.changed_cells[index]]))
+            .collect::<Vec<_>>();
+
+        json!({
+            "version": CHECKPOINT_VERSION,
+            "rows": size.row,
+            "columns": size.column,
+            "row_hints": &self.given_hint[..size.row],
+            "column_hints": &self.given_hint[size.row..],
+            "board": self.board.to_rows(),
+            "possibilities": possibilities,
+            "queue": queue,
+            "tick": self.line_queue.tick(),
+            "heuristic": self.heuristic.as_str(),
+            "line_passes": self.budget.line_passes,
+            "placements": self.placements(),
+        })
+    }
+
+    /// Writes [`Solver::save_state`] to `path`, through a temporary file next to it.
+    pub fn save_state_to(&self, path: &Path) -> Result<(), SolverError> {
+        let mut temp_path = path.as_os_str().to_owned();
+        temp_path.push(".tmp");
+        let temp_path = PathBuf::from(temp_path);
+
+        let write = || -> std::io::Result<()> {
+            let mut file = fs::File::create(&temp_path)?;
+            serde_json::to_writer(&mut file, &self.save_state())?;
+            file.flush()?;
+            fs::rename(&temp_path, path)
+        };
+        write().map_err(|e| {
+            SolverError::Checkpoint(format!("Failed to write {}: {}", path.display(), e))
+        })
+    }
+
+    /// Creates a solver that continues from a state written by [`Solver::save_state`].
+    pub fn load_state(state: &Value, display: Box<dyn SolverDisplay>) -> Result<Self, SolverError> {
+        let invalid =
+            |message: &str| SolverError::Checkpoint(format!("Invalid checkpoint: {}", message));
+
+        if state["version"].as_u64() != Some(CHECKPOINT_VERSION) {
+            return Err(invalid("unsupported version"));
+        }
+        let size = Vec2::new(
+            usize_field(&state["rows"]).ok_or_else(|| invalid("missing rows"))?,
+            usize_field(&state["columns"]).ok_or_else(|| invalid("missing columns"))?,
+        );
+        let row_hints = hints_field(&state["row_hints"]).ok_or_else(|| invalid("bad row_hints"))?;
+        let column_hints =
+            hints_field(&state["column_hints"]).ok_or_else(|| invalid("bad column_hints"))?;
+
+        let mut solver = Solver::new(size, row_hints, column_hints, display)?;
+
+        let heuristic = state["heuristic"]
+            .as_str()
+            .and_then(|name| LineHeuristic::ALL.into_iter().find(|h| h.as_str() == name))
+            .ok_or_else(|| invalid("unknown heuristic"))?;
+        solver.heuristic = heuristic;
+
+        let rows = state["board"]
+            .as_array()
+            .filter(|rows| rows.len() == size.row)
+            .ok_or_else(|| invalid("bad board"))?;
+        for (row, text) in rows.iter().enumerate() {
+            let text = text
+                .as_str()
+                .filter(|text| text.chars().count() == size.column)
+                .ok_or_else(|| invalid("bad board row"))?;
+            for (column, ch) in text.chars().enumerate() {
+                let cell = match ch {
+                    '#' => Cell::Block,
+                    '.' => Cell::Blank,
+                    '?' => Cell::Unknown,
+                    _ => return Err(invalid("bad board cell")),
+                };
+                solver.write_cell(Vec2::new(row, column), cell);
+            }
+        }
+
+        let possibilities = state["possibilities"]
+            .as_array()
+            .filter(|lines| lines.len() == solver.possibilities.len())
+            .ok_or_else(|| invalid("bad possibilities"))?;
+        for (index, line) in possibilities.iter().enumerate() {
+            let ranges = line
+                .as_array()
+                .and_then(|ranges| ranges.iter().map(range_field).collect::<Option<Vec<_>>>())
+                .ok_or_else(|| invalid("bad possibilities"))?;
+            solver.possibilities[index] =
+                PlacementSet::from_ranges(ranges, solver.possibilities[index].len())
+                    .ok_or_else(|| invalid("possibilities out of range"))?;
+            solver.fill_ratio_cache[index] = None;
+        }
+
+        // 저장된 순서 그대로 큐를 다시 만든다
+        let queue = state["queue"]
+            .as_array()
+            .ok_or_else(|| invalid("bad queue"))?;
+        solver.line_queue.clear();
+        solver.changed_cells.iter_mut().for_each(|count| *count = 0);
+        for entry in queue {
+            let (index, cost, changed) = match entry.as_array().map(Vec::as_slice) {
+                Some([index, cost, changed]) => (
+                    usize_field(index).filter(|&index| index < solver.changed_cells.len()),
+                    cost.as_u64(),
+                    usize_field(changed),
+                ),
+                _ => (None, None, None),
+            };
+            let (Some(index), Some(cost), Some(changed)) = (index, cost, changed) else {
+                return Err(invalid("bad queue entry"));
+            };
+            solver.line_queue.push(index, cost);
+            solver.changed_cells[index] = changed;
+        }
+        let tick = state["tick"]
+            .as_u64()
+            .ok_or_else(|| invalid("missing tick"))?;
+        solver.line_queue.advance_tick(tick);
+
+        solver.budget.line_passes = usize_field(&state["line_passes"]).unwrap_or(0);
+        solver.budget.placements.store(
+            usize_field(&state["placements"]).unwrap_or(0),
+            Ordering::Relaxed,
+        );
+
+        Ok(solver)
+    }
+
+    /// Reads a state written by [`Solver::save_state_to`].
+    pub fn load_state_from(
+        path: &Path,
+        display: Box<dyn SolverDisplay>,
+    ) -> Result<Self, SolverError> {
+        let text = fs::read_to_string(path).map_err(|e| {
+            SolverError::Checkpoint(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        let state = serde_json::from_str(&text).map_err(|e| {
+            SolverError::Checkpoint(format!("Failed to parse {}: {}", path.display(), e))
+        })?;
+        Self::load_state(&state, display)
+    }
+
+    /// Saves the state if the checkpoint interval has passed, or always if `force` is set.
+    pub(super) fn save_checkpoint(&mut self, force: bool) -> Result<(), SolverError> {
+        let Some(schedule) = &self.checkpoint_schedule else {
+            return Ok(());
+        };
+        // 가정한 칸이 섞인 보드는 저장하지 않는다
+        if self.speculation_depth > 0 {
+            return Ok(());
+        }
+        if !force && schedule.last_saved.elapsed() < schedule.interval {
+            return Ok(());
+        }
+        self.save_state_to(&schedule.path)?;
+        if let Some(schedule) = &mut self.checkpoint_schedule {
+            schedule.last_saved = Instant::now();
+        }
+        Ok(())
+    }
+}
+
+fn usize_field(value: &Value) -> Option<usize> {
+    value.as_u64().and_then(|value| usize::try_from(value).ok())
+}
+
+fn hints_field(value: &Value) -> Option<Vec<Vec<usize>>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|hint| hint.as_array()?.iter().map(usize_field).collect())
+        .collect()
+}
+
+fn range_field(value: &Value) -> Option<Range<usize>> {
+    match value.as_array()?.as_slice() {
+        [start, end] => Some(usize_field(start)?..usize_field(end)?),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::NullDisplay;
+    use crate::solver::limits::{AbortReason, SolveLimits};
+    use crate::solver::parser::{FileSolverParser, SolverParser};
+    use crate::solver::types::{Line, LineDirection, LineSolvingInfoProvider};
+
+    fn create_solver() -> Solver {
+        FileSolverParser::new("./sample/data1.txt")
+            .create_solver(Box::new(NullDisplay))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_resume_matches_uninterrupted_solve() {
+        let mut expected = create_solver();
+        expected.set_line_heuristic(LineHeuristic::RoundRobin);
+        expected.solve().unwrap();
+
+        let mut solver = create_solver();
+        solver.set_line_heuristic(LineHeuristic::RoundRobin);
+        for _ in 0..7 {
+            solver.solve_step().unwrap();
+        }
+        let state = solver.save_state();
+
+        let mut resumed = Solver::load_state(&state, Box::new(NullDisplay)).unwrap();
+        assert_eq!(resumed.save_state(), state);
+        assert_eq!(resumed.line_order(), solver.line_order());
+        resumed.solve().unwrap();
+        assert!(resumed.board().iter_all().eq(expected.board().iter_all()));
+        assert_eq!(resumed.line_passes(), expected.line_passes());
+    }
+
+    #[test]
+    fn test_save_state_to_file() {
+        let path = std::env::temp_dir().join(format!(
+            "nonogram_solver_checkpoint_{}.json",
+            std::process::id()
+        ));
+        let mut solver = create_solver();
+        solver.set_checkpoint(&path, Duration::ZERO);
+        solver.solve_step().unwrap();
+
+        let resumed = Solver::load_state_from(&path, Box::new(NullDisplay)).unwrap();
+        let line = Line::new(LineDirection::Row, 0);
+        assert_eq!(
+            resumed.possibilities[resumed.line_to_index(line)],
+            solver.possibilities[solver.line_to_index(line)]
+        );
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_abort_keeps_reason_when_save_fails() {
+        let mut solver = create_solver();
+        solver.set_checkpoint(
+            std::env::temp_dir().join("nonogram_solver_missing/checkpoint.json"),
+            Duration::from_secs(3600),
+        );
+        solver.set_limits(SolveLimits {
+            max_line_passes: Some(3),
+            ..Default::default()
+        });
+
+        let Err(SolverError::Aborted(e)) = solver.solve() else {
+            panic!("Expected the solver to abort");
+        };
+        assert_eq!(e.reason, AbortReason::LinePassLimit);
+        assert!(e.checkpoint_error.is_some());
+    }
+
+    #[test]
+    fn test_invalid_state() {
+        let mut state = create_solver().save_state();
+        state["board"][0] = json!("nope");
+        assert!(matches!(
+            Solver::load_state(&state, Box::new(NullDisplay)),
+            Err(SolverError::Checkpoint(_))
+        ));
+    }
+}
@@ -1,12 +1,18 @@
 use std::{error::Error, fmt::Display};
 
-use super::{cell::Cell, types::Line};
+use super::{cell::Cell, limits::AbortReason, types::Line};
+use crate::board::Board;
 
 #[derive(Debug)]
 pub enum SolverError {
     InvalidBoardSize(usize, usize),
     InvalidInitialInfo(InvalidInfoError),
     InvalidSolvingState(SolvingError),
+    Aborted(AbortedError),
+    /// The hints cannot be satisfied by the cells deduced so far.
+    Contradiction(Line),
+    /// A checkpoint could not be written or read.
+    Checkpoint(String),
 }
 
 impl Display for SolverError {
@@ -17,6 +23,9 @@ impl Display for SolverError {
             }
             SolverError::InvalidInitialInfo(e) => write!(f, "Invalid initial info: {:?}", e),
             SolverError::InvalidSolvingState(e) => write!(f, "Solving error: {:?}", e),
+            SolverError::Aborted(e) => write!(f, "Solving aborted: {}", e.reason),
+            SolverError::Contradiction(line) => write!(f, "Contradiction in line {:?}", line),
+            SolverError::Checkpoint(message) => write!(f, "Checkpoint error: {}", message),
         }
     }
 }
@@ -39,6 +48,15 @@ pub struct SolvingError {
     pub message: String,
 }
 
+/// The solver stopped early. `board` holds everything deduced until then.
+#[derive(Debug)]
+pub struct AbortedError {
+    pub reason: AbortReason,
+    pub board: Board<Cell>,
+    /// Why the stopped state could not be saved to the checkpoint file, if one was set.
+    pub checkpoint_error: Option<String>,
+}
+
 impl From<SolverError> for String {
     fn from(e: SolverError) -> Self {
         e.to_string()
@@ -0,0 +1,123 @@
+use super::bit_line::BitLine;
+use super::cell::Cell;
+use super::types::{Line, LineDirection, LineSolvingInfoProvider};
+use super::Solver;
+use crate::board::{Board, Vec2};
+
+/// Share of the placements that fill a cell, among the surviving placements of its row and of
+/// its column that fit the current cells.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct CellHeat {
+    pub row: f32,
+    pub column: f32,
+}
+
+impl Solver {
+    /// For each cell of `line`, the share of its surviving placements that fit the current cells
+    /// and fill the cell. All zero if no placement fits.
+    ///
+    /// Lines are recounted only after their cells or placements change.
+    pub fn fill_ratios(&mut self, line: Line) -> &[f32] {
+        let index = self.line_to_index(line);
+        if self.fill_ratio_cache[index].is_none() {
+            let ratios = self.count_fill_ratios(index);
+            self.fill_ratio_cache[index] = Some(ratios);
+        }
+        self.fill_ratio_cache[index].as_deref().unwrap_or_default()
+    }
+
+    fn count_fill_ratios(&mut self, index: usize) -> Vec<f32> {
+        let current = &self.line_bits[index];
+        let length = current.len();
+        let hint = &self.given_hint[index];
+        let mut filled = vec![0usize; length];
+        let mut fitting = 0usize;
+
+        if !hint.is_empty() {
+            let mut placement = BitLine::new(length);
+            for possibility_index in self.possibilities[index].iter() {
+                let placed = self.calculator.calc_placement_line(
+                    hint,
+                    length,
+                    possibility_index,
+                    &mut placement,
+                );
+                if placed.is_err() || !current.fits(&placement) {
+                    continue;
+                }
+                fitting += 1;
+                for (cell, count) in filled.iter_mut().enumerate() {
+                    if placement.get(cell) == Cell::Block {
+                        *count += 1;
+                    }
+                }
+            }
+        }
+
+        filled
+            .into_iter()
+            .map(|count| match fitting {
+                0 => 0.0,
+                _ => count as f32 / fitting as f32,
+            })
+            .collect()
+    }
+
+    /// [`Solver::fill_ratios`] of every row and column, by cell.
+    pub fn heatmap(&mut self) -> Board<CellHeat> {
+        let size = self.board.size();
+        let mut heatmap = Board::new(size, CellHeat::default());
+        for row in 0..size.row {
+            let ratios = self.fill_ratios(Line::new(LineDirection::Row, row));
+            for (column, &ratio) in ratios.iter().enumerate() {
+                heatmap.value_mut(Vec2::new(row, column)).row = ratio;
+            }
+        }
+        for column in 0..size.column {
+            let ratios = self.fill_ratios(Line::new(LineDirection::Column, column));
+            for (row, &ratio) in ratios.iter().enumerate() {
+                heatmap.value_mut(Vec2::new(row, column)).column = ratio;
+            }
+        }
+        heatmap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::NullDisplay;
+
+    #[test]
+    fn test_fill_ratios() {
+        let mut solver = Solver::new(
+            Vec2::new(2, 3),
+            vec![vec![2], vec![1]],
+            vec![vec![1], vec![1], vec![1]],
+            Box::new(NullDisplay),
+        )
+        .unwrap();
+        let row = Line::new(LineDirection::Row, 0);
+        // `2`는 세 칸에 두 가지로 놓인다
+        assert_eq!(solver.fill_ratios(row), &[0.5, 1.0, 0.5]);
+        assert_eq!(
+            solver.fill_ratios(Line::new(LineDirection::Column, 0)),
+            &[0.5, 0.5]
+        );
+
+        solver.set_cell(Vec2::new(0, 0), Cell::Blank);
+        assert_eq!(solver.fill_ratios(row), &[0.0, 1.0, 1.0]);
+
+        solver.solve().unwrap();
+        assert!(solver.is_solved());
+        let heatmap = solver.heatmap();
+        assert_eq!(
+            *heatmap.value(Vec2::new(1, 0)),
+            CellHeat {
+                row: 1.0,
+                column: 1.0
+            }
+        );
+        assert_eq!(heatmap.value(Vec2::new(1, 1)).row, 0.0);
+    }
+}
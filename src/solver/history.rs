@@ -0,0 +1,284 @@
+use std::ops::Range;
+
+use super::cell::Cell;
+use super::types::{Line, LineDirection, LineSolvingInfoProvider};
+use super::Solver;
+use crate::board::Vec2;
+
+/// A cell changed by a deduction or by [`Solver::set_cell`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CellChange {
+    pub position: Vec2,
+    pub old: Cell,
+    pub new: Cell,
+    /// Line whose deduction changed the cell, or `None` if it was set from outside.
+    pub line: Option<Line>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Change {
+    Cell(CellChange),
+    /// Placements of `line` that no longer fit, as sorted index ranges.
+    Placements {
+        line: Line,
+        removed: Vec<Range<usize>>,
+    },
+}
+
+/// Point in the change log returned by [`Solver::checkpoint`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct CheckpointId {
+    /// Which log the position belongs to; a new one starts after the last checkpoint is released.
+    generation: usize,
+    position: usize,
+}
+
+/// Changes are only recorded while a checkpoint is open, so a plain solve keeps no log.
+#[derive(Default)]
+pub(crate) struct History {
+    done: Vec<Change>,
+    /// Undone changes, the most recently undone last.
+    undone: Vec<Change>,
+    /// Positions of the checkpoints that were not released yet.
+    open: Vec<usize>,
+    generation: usize,
+}
+
+impl History {
+    fn is_open(&self, id: CheckpointId) -> bool {
+        id.generation == self.generation && self.open.contains(&id.position)
+    }
+}
+
+impl Solver {
+    /// Changes made since the oldest open checkpoint, oldest first.
+    pub fn history(&self) -> &[Change] {
+        &self.history.done
+    }
+
+    /// Marks the current state so that [`Solver::undo_to`] can come back to it, and records
+    /// changes until every checkpoint is released with [`Solver::release`]. Unlike
+    /// [`Solver::set_checkpoint`], this keeps nothing but a position in the change log.
+    pub fn checkpoint(&mut self) -> CheckpointId {
+        let position = self.history.done.len();
+        self.history.open.push(position);
+        CheckpointId {
+            generation: self.history.generation,
+            position,
+        }
+    }
+
+    /// Closes `id`, keeping the changes made since. The log is dropped once no checkpoint is
+    /// open, and the ids it handed out stop working.
+    pub fn release(&mut self, id: CheckpointId) {
+        let history = &mut self.history;
+        if id.generation != history.generation {
+            return;
+        }
+        if let Some(index) = history.open.iter().rposition(|&open| open == id.position) {
+            history.open.remove(index);
+        }
+        if history.open.is_empty() {
+            history.done = Vec::new();
+            history.undone = Vec::new();
+            history.generation += 1;
+        }
+    }
+
+    /// Reverts every change made after `id`. Returns `false` if `id` is ahead of the current
+    /// state or was released.
+    ///
+    /// Lines touched by the reverted changes are queued again. Solving counters are kept.
+    pub fn undo_to(&mut self, id: CheckpointId) -> bool {
+        if !self.history.is_open(id) || id.position > self.history.done.len() {
+            return false;
+        }
+        while self.history.done.len() > id.position {
+            let Some(change) = self.history.done.pop() else {
+                break;
+            };
+            self.revert_change(&change, true);
+            self.history.undone.push(change);
+        }
+        true
+    }
+
+    /// Applies undone changes again until the state is the one at `id`. Returns `false` if `id`
+    /// was not undone, for example because something changed since, or was released.
+    pub fn redo_to(&mut self, id: CheckpointId) -> bool {
+        if !self.history.is_open(id)
+            || id.position < self.history.done.len()
+            || id.position > self.history.done.len() + self.history.undone.len()
+        {
+            return false;
+        }
+        while self.history.done.len() < id.position {
+            let Some(change) = self.history.undone.pop() else {
+                break;
+            };
+            self.revert_change(&change, false);
+            self.history.done.push(change);
+        }
+        true
+    }
+
+    /// Undoes `change`, or applies it again if `undo` is false.
+    fn revert_change(&mut self, change: &Change, undo: bool) {
+        match change {
+            Change::Cell(change) => {
+                self.write_cell(change.position, if undo { change.old } else { change.new });
+                self.queue_line(Line::new(LineDirection::Row, change.position.row));
+                self.queue_line(Line::new(LineDirection::Column, change.position.column));
+            }
+            Change::Placements { line, removed } => {
+                let index = self.line_to_index(*line);
+                if undo {
+                    self.possibilities[index].insert(removed);
+                } else {
+                    self.possibilities[index].remove(removed);
+                }
+                self.fill_ratio_cache[index] = None;
+                self.queue_line(*line);
+            }
+        }
+    }
+
+    /// Changes a cell and records it. `line` is the line whose deduction caused the change.
+    pub(super) fn change_cell(&mut self, position: Vec2, cell: Cell, line: Option<Line>) {
+        let old = *self.board.value(position);
+        self.write_cell(position, cell);
+        self.record_change(Change::Cell(CellChange {
+            position,
+            old,
+            new: cell,
+            line,
+        }));
+    }
+
+    pub(super) fn remove_placements(&mut self, line: Line, removed: Vec<Range<usize>>) {
+        if removed.is_empty() {
+            return;
+        }
+        let index = self.line_to_index(line);
+        self.possibilities[index].remove(&removed);
+        self.fill_ratio_cache[index] = None;
+        self.record_change(Change::Placements { line, removed });
+    }
+
+    fn record_change(&mut self, change: Change) {
+        if self.history.open.is_empty() {
+            return;
+        }
+        // 새로 바뀐 것이 생기면 되돌린 변경은 다시 적용할 수 없다
+        self.history.undone.clear();
+        self.history.done.push(change);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::NullDisplay;
+    use crate::solver::error::SolverError;
+    use crate::solver::parser::{FileSolverParser, SolverParser};
+
+    fn create_solver() -> Solver {
+        FileSolverParser::new("./sample/data1.txt")
+            .create_solver(Box::new(NullDisplay))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_undo_and_redo() {
+        let mut solver = create_solver();
+        for _ in 0..5 {
+            solver.solve_step().unwrap();
+        }
+        let start = solver.checkpoint();
+        let board = solver.board().clone();
+        let state = solver.save_state();
+
+        solver.solve().unwrap();
+        assert!(solver.is_solved());
+        let solved = solver.checkpoint();
+        assert!(solver
+            .history()
+            .iter()
+            .any(|change| matches!(change, Change::Cell(CellChange { line: Some(_), .. }))));
+
+        assert!(solver.undo_to(start));
+        assert!(solver.board().iter_all().eq(board.iter_all()));
+        assert_eq!(solver.save_state()["possibilities"], state["possibilities"]);
+        assert!(!solver.undo_to(solved));
+
+        assert!(solver.redo_to(solved));
+        assert!(solver.is_solved());
+
+        // 되돌린 뒤 다시 풀어도 같은 해가 나온다
+        assert!(solver.undo_to(start));
+        solver.solve().unwrap();
+        assert!(solver.is_solved());
+
+        solver.release(solved);
+        assert!(!solver.history().is_empty());
+        solver.release(start);
+        assert!(solver.history().is_empty());
+        assert!(!solver.undo_to(start));
+    }
+
+    #[test]
+    fn test_released_checkpoint() {
+        let mut solver = create_solver();
+        let start = solver.checkpoint();
+        for _ in 0..3 {
+            solver.solve_step().unwrap();
+        }
+        let middle = solver.checkpoint();
+        solver.solve().unwrap();
+
+        // 마지막이 아닌 체크포인트도 놓으면 더는 쓸 수 없다
+        solver.release(middle);
+        assert!(!solver.history().is_empty());
+        assert!(!solver.undo_to(middle));
+        assert!(solver.undo_to(start));
+        assert!(!solver.redo_to(middle));
+    }
+
+    #[test]
+    fn test_no_history_without_checkpoint() {
+        let mut solver = create_solver();
+        solver.solve().unwrap();
+        assert!(solver.is_solved());
+        assert!(solver.history().is_empty());
+    }
+
+    #[test]
+    fn test_backtrack_guess() {
+        // 두 가지 해가 있는 퍼즐에서 추측을 되돌리고 다른 값을 시도한다
+        let mut solver = Solver::new(
+            Vec2::new(2, 2),
+            vec![vec![1], vec![1]],
+            vec![vec![1], vec![1]],
+            Box::new(NullDisplay),
+        )
+        .unwrap();
+        solver.solve().unwrap();
+        let before_guess = solver.checkpoint();
+
+        solver.set_cell(Vec2::new(0, 0), Cell::Block);
+        solver.solve().unwrap();
+        assert_eq!(*solver.board().value(Vec2::new(1, 1)), Cell::Block);
+
+        assert!(solver.undo_to(before_guess));
+        assert_eq!(*solver.board().value(Vec2::new(0, 0)), Cell::Unknown);
+        solver.set_cell(Vec2::new(0, 0), Cell::Blank);
+        solver.solve().unwrap();
+        assert_eq!(*solver.board().value(Vec2::new(0, 1)), Cell::Block);
+        assert!(solver.is_solved());
+
+        assert!(solver.undo_to(before_guess));
+        solver.set_cell(Vec2::new(0, 0), Cell::Block);
+        solver.set_cell(Vec2::new(0, 1), Cell::Block);
+        assert!(matches!(solver.solve(), Err(SolverError::Contradiction(_))));
+    }
+}
@@ -0,0 +1,108 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Bounds on how much work [`Solver::solve`](super::Solver::solve) may do.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct SolveLimits {
+    pub deadline: Option<Instant>,
+    pub max_line_passes: Option<usize>,
+    pub max_placements: Option<usize>,
+}
+
+/// Flag shared between threads to stop a running solver.
+#[derive(Clone, Default, Debug)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AbortReason {
+    Cancelled,
+    Timeout,
+    LinePassLimit,
+    PlacementLimit,
+}
+
+impl std::fmt::Display for AbortReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                AbortReason::Cancelled => "cancelled",
+                AbortReason::Timeout => "deadline exceeded",
+                AbortReason::LinePassLimit => "line pass limit reached",
+                AbortReason::PlacementLimit => "placement limit reached",
+            }
+        )
+    }
+}
+
+/// Number of placements tried between checks of the deadline and cancellation token.
+const LIMIT_CHECK_INTERVAL: usize = 256;
+
+/// Work done so far, checked against [`SolveLimits`].
+#[derive(Default, Debug)]
+pub(crate) struct SolveBudget {
+    pub limits: SolveLimits,
+    pub cancellation_token: CancellationToken,
+    pub line_passes: usize,
+    // 여러 스레드가 함께 세므로 atomic
+    pub placements: AtomicUsize,
+}
+
+impl SolveBudget {
+    pub fn count_line_pass(&mut self) -> Result<(), AbortReason> {
+        if self
+            .limits
+            .max_line_passes
+            .is_some_and(|max| self.line_passes >= max)
+        {
+            return Err(AbortReason::LinePassLimit);
+        }
+        self.line_passes += 1;
+        Ok(())
+    }
+
+    /// Counts the `i`-th placement of a line. The clock and token are only read every few calls.
+    pub fn count_placement(&self, i: usize) -> Result<(), AbortReason> {
+        if self
+            .limits
+            .max_placements
+            .is_some_and(|max| self.placements.load(Ordering::Relaxed) >= max)
+        {
+            return Err(AbortReason::PlacementLimit);
+        }
+
+        if i.is_multiple_of(LIMIT_CHECK_INTERVAL) {
+            if self.cancellation_token.is_cancelled() {
+                return Err(AbortReason::Cancelled);
+            }
+            if self
+                .limits
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+            {
+                return Err(AbortReason::Timeout);
+            }
+        }
+
+        self.placements.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+}
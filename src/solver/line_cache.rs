@@ -0,0 +1,129 @@
+use std::borrow::Borrow;
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroUsize;
+
+use lru::LruCache;
+
+use super::bit_line::BitLine;
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct LineCacheStats {
+    pub hits: usize,
+    pub misses: usize,
+    /// Number of cached lines.
+    pub len: usize,
+}
+
+/// Hint and cells of a line, so that the owned keys of the cache can be looked up with
+/// borrowed ones.
+trait LineKey {
+    fn key(&self) -> (&[usize], &BitLine);
+}
+
+impl LineKey for (Vec<usize>, BitLine) {
+    fn key(&self) -> (&[usize], &BitLine) {
+        (&self.0, &self.1)
+    }
+}
+
+impl LineKey for (&[usize], &BitLine) {
+    fn key(&self) -> (&[usize], &BitLine) {
+        *self
+    }
+}
+
+impl<'a> Borrow<dyn LineKey + 'a> for (Vec<usize>, BitLine) {
+    fn borrow(&self) -> &(dyn LineKey + 'a) {
+        self
+    }
+}
+
+// `Vec`과 슬라이스의 해시가 같아서 빌린 키로도 같은 항목을 찾는다
+impl Hash for dyn LineKey + '_ {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl PartialEq for dyn LineKey + '_ {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for dyn LineKey + '_ {}
+
+/// Deduced lines keyed by hint and the cells known before solving.
+///
+/// The placements that fit a line depend only on its hint and cells, so a line with the same
+/// hint and cells always deduces the same result.
+pub(crate) struct LineCache {
+    cache: LruCache<(Vec<usize>, BitLine), BitLine>,
+    hits: usize,
+    misses: usize,
+}
+
+impl LineCache {
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            cache: LruCache::new(capacity),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub fn get(&mut self, hint: &[usize], current_line: &BitLine) -> Option<BitLine> {
+        let key: &dyn LineKey = &(hint, current_line);
+        let result = self.cache.get(key).cloned();
+        match result {
+            Some(_) => self.hits += 1,
+            None => self.misses += 1,
+        }
+        result
+    }
+
+    pub fn insert(&mut self, hint: &[usize], current_line: BitLine, new_line: BitLine) {
+        self.cache.put((hint.to_vec(), current_line), new_line);
+    }
+
+    pub fn stats(&self) -> LineCacheStats {
+        LineCacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            len: self.cache.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::Cell::{self, *};
+
+    fn line(cells: &[Cell]) -> BitLine {
+        BitLine::from_cells(cells)
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut cache = LineCache::new(NonZeroUsize::new(2).unwrap());
+        cache.insert(&[1], line(&[Unknown, Unknown]), line(&[Unknown, Unknown]));
+        cache.insert(&[2], line(&[Unknown, Unknown]), line(&[Block, Block]));
+        assert!(cache.get(&[1], &line(&[Unknown, Unknown])).is_some());
+
+        cache.insert(&[1], line(&[Block, Unknown]), line(&[Block, Blank]));
+        assert!(cache.get(&[2], &line(&[Unknown, Unknown])).is_none());
+        assert_eq!(
+            cache.get(&[1], &line(&[Block, Unknown])),
+            Some(line(&[Block, Blank]))
+        );
+        assert_eq!(
+            cache.stats(),
+            LineCacheStats {
+                hits: 2,
+                misses: 1,
+                len: 2,
+            }
+        );
+    }
+}
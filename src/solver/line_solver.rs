@@ -0,0 +1,136 @@
+use std::ops::Range;
+
+use super::bit_line::{BitLine, PlacementIntersection};
+use super::calculator::NumberDistributionCalculator;
+use super::error::SolvingError;
+use super::limits::AbortReason;
+use super::placements::{push_index, PlacementSet};
+use super::types::Line;
+use crate::generator::line_hint;
+
+/// Result of matching the surviving placements of a line against its current cells.
+pub(crate) struct LineDeduction {
+    /// Cells decided by every fitting placement; the others are unknown.
+    pub new_line: BitLine,
+    /// Placements that contradict the current cells, in order.
+    pub removed: Vec<Range<usize>>,
+}
+
+pub(crate) enum DeduceError {
+    Aborted(AbortReason),
+    /// No placement of the hint fits the current cells.
+    Contradiction,
+    Invalid(SolvingError),
+}
+
+/// Intersects every placement of `hint` that fits `current_line`.
+///
+/// This only reads its inputs, so lines that share no cells can be deduced at the same time.
+/// `on_placement` is called with `(i, total)` before each placement and can stop the deduction.
+pub(crate) fn deduce_line(
+    calculator: &mut NumberDistributionCalculator,
+    line: Line,
+    hint: &[usize],
+    current_line: &BitLine,
+    possibilities: &PlacementSet,
+    mut on_placement: impl FnMut(usize, usize) -> Result<(), AbortReason>,
+) -> Result<LineDeduction, DeduceError> {
+    let line_length = current_line.len();
+    let mut removed = Vec::new();
+
+    if current_line.is_complete() {
+        let expected = hint.iter().copied().filter(|&number| number > 0);
+        if !line_hint(&current_line.to_cells()).into_iter().eq(expected) {
+            return Err(DeduceError::Contradiction);
+        }
+        return Ok(LineDeduction {
+            new_line: current_line.clone(),
+            removed,
+        });
+    }
+
+    if hint.is_empty() {
+        let new_line = BitLine::blank(line_length);
+        if !current_line.fits(&new_line) {
+            return Err(DeduceError::Contradiction);
+        }
+        return Ok(LineDeduction { new_line, removed });
+    }
+
+    let total_possibilities = possibilities.len();
+    let mut intersection = PlacementIntersection::new(line_length);
+    let mut placement = BitLine::new(line_length);
+    let mut removed_count = 0;
+
+    for (i, possibility_index) in possibilities.iter().enumerate() {
+        on_placement(i, total_possibilities).map_err(DeduceError::Aborted)?;
+
+        calculator
+            .calc_placement_line(hint, line_length, possibility_index, &mut placement)
+            .map_err(|e| {
+                DeduceError::Invalid(SolvingError {
+                    current_line: current_line.to_cells(),
+                    calculating_line: placement.to_cells(),
+                    hint: hint.to_vec(),
+                    error_line: line,
+                    message: e,
+                })
+            })?;
+
+        if !current_line.fits(&placement) {
+            push_index(&mut removed, possibility_index);
+            removed_count += 1;
+            continue;
+        }
+
+        intersection.add(&placement);
+        if intersection.is_exhausted(current_line) {
+            break;
+        }
+    }
+
+    if removed_count == total_possibilities {
+        return Err(DeduceError::Contradiction);
+    }
+
+    Ok(LineDeduction {
+        new_line: intersection.to_line(),
+        removed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::cell::Cell;
+    use crate::solver::types::LineDirection;
+
+    fn deduce(hint: &[usize], current_line: &BitLine) -> Result<LineDeduction, DeduceError> {
+        let mut calculator = NumberDistributionCalculator::new();
+        let count = calculator.calc_distribute_count_line_hint(hint, current_line.len());
+        deduce_line(
+            &mut calculator,
+            Line::new(LineDirection::Column, 0),
+            hint,
+            current_line,
+            &PlacementSet::full(count),
+            |_, _| Ok(()),
+        )
+    }
+
+    #[test]
+    fn test_empty_hint() {
+        let mut current_line = BitLine::new(3);
+        let Ok(deduction) = deduce(&[], &current_line) else {
+            panic!("An empty line should fit an empty hint");
+        };
+        assert_eq!(deduction.new_line.to_cells(), vec![Cell::Blank; 3]);
+
+        // 빈 힌트인데 이미 채워진 칸이 있다
+        current_line.set(0, Cell::Block);
+        assert!(matches!(
+            deduce(&[], &current_line),
+            Err(DeduceError::Contradiction)
+        ));
+    }
+}
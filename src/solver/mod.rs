@@ -1,32 +1,72 @@
+pub mod bit_line;
 pub mod calculator;
 mod cell;
+mod checkpoint;
 pub mod error;
+pub mod heatmap;
+pub mod history;
+pub mod limits;
+pub mod line_cache;
+mod line_solver;
+#[cfg(feature = "parallel")]
+mod parallel;
 pub mod parser;
+mod placements;
+pub mod probing;
+pub mod sat;
+pub mod scheduler;
 pub mod solver_display;
 pub mod types;
 
 pub use cell::Cell;
 
 use crate::board::{Board, Vec2};
-use bit_set::BitSet;
+use bit_line::BitLine;
 use calculator::NumberDistributionCalculator;
-use error::{InvalidInfoError, SolverError, SolvingError};
-use solver_display::{SolverDisplay, SolverState, SolvingContext};
-use std::collections::HashSet;
+use checkpoint::CheckpointSchedule;
+use error::{AbortedError, InvalidInfoError, SolverError};
+use history::History;
+use limits::{AbortReason, CancellationToken, SolveBudget, SolveLimits};
+use line_cache::{LineCache, LineCacheStats};
+use line_solver::{deduce_line, DeduceError, LineDeduction};
+use placements::PlacementSet;
+use probing::SolveLevel;
+use scheduler::{LineHeuristic, LineQueue, LineStats};
+use solver_display::{SolverDisplay, SolverState, SolvingContext, WAITING_LINES};
+use std::num::NonZeroUsize;
+use std::sync::atomic::Ordering;
 use types::{Line, LineDirection, LineProcessor, LineSolvingInfoProvider};
+
 pub struct Solver {
     // Fixed
     given_hint: Vec<Vec<usize>>,
     display: Box<dyn SolverDisplay>,
 
     // Mutable
-    pub board: Board<Cell>,
-    possibilities: Vec<BitSet>,
+    /// Changed through [`Solver::change_cell`] and [`Solver::write_cell`] only, which keep the
+    /// line masks, counted ratios and the change log in sync.
+    board: Board<Cell>,
+    possibilities: Vec<PlacementSet>,
 
     // Cache
-    possibility_count: Vec<usize>,
-    line_changed: HashSet<Line>,
+    /// `board` as bit masks, indexed like `possibilities`.
+    line_bits: Vec<BitLine>,
+    /// Counted by [`Solver::fill_ratios`], indexed like `possibilities`.
+    fill_ratio_cache: Vec<Option<Vec<f32>>>,
+    changed_cells: Vec<usize>,
+    line_queue: LineQueue,
+    heuristic: LineHeuristic,
+    level: SolveLevel,
+    /// Number of guesses the board currently depends on while probing or searching.
+    speculation_depth: usize,
     calculator: NumberDistributionCalculator,
+    line_cache: Option<LineCache>,
+
+    // Limits
+    budget: SolveBudget,
+    checkpoint_schedule: Option<CheckpointSchedule>,
+
+    history: History,
 }
 
 impl Solver {
@@ -35,25 +75,51 @@ impl Solver {
         hints: &[Vec<usize>],
         direction: LineDirection,
     ) -> Result<(), SolverError> {
+        let invalid = |idx: usize, message: &str| {
+            SolverError::InvalidInitialInfo(InvalidInfoError {
+                error_line: Line::new(direction, idx),
+                size,
+                message: message.to_string(),
+            })
+        };
+
         for (idx, hint) in hints.iter().enumerate() {
             if hint.is_empty() {
                 continue;
             }
-            let sum: usize = hint.iter().sum();
-            let spaces_needed = sum + hint.len() - 1;
-            if spaces_needed > size {
-                return Err(SolverError::InvalidInitialInfo(InvalidInfoError {
-                    error_line: Line::new(direction, idx),
-                    size,
-                    message: "Invalid hint: required space for hint is larger than the board size"
-                        .to_string(),
-                }));
+            let spaces_needed = hint
+                .iter()
+                .try_fold(hint.len() - 1, |acc, &n| acc.checked_add(n));
+            if spaces_needed.is_none_or(|spaces_needed| spaces_needed > size) {
+                return Err(invalid(
+                    idx,
+                    "Invalid hint: required space for hint is larger than the board size",
+                ));
             }
         }
 
         Ok(())
     }
 
+    fn validate_hint_count(
+        count: usize,
+        hints: &[Vec<usize>],
+        direction: LineDirection,
+    ) -> Result<(), SolverError> {
+        if hints.len() == count {
+            return Ok(());
+        }
+        Err(SolverError::InvalidInitialInfo(InvalidInfoError {
+            error_line: Line::new(direction, hints.len().min(count)),
+            size: count,
+            message: format!(
+                "Invalid hint: expected {} hints but got {}",
+                count,
+                hints.len()
+            ),
+        }))
+    }
+
     pub fn new(
         size: Vec2,
         row_hint: Vec<Vec<usize>>,
@@ -62,14 +128,17 @@ impl Solver {
     ) -> Result<Self, SolverError> {
         display.change_state(SolverState::Loading("Validating board size.".to_string()));
 
-        if size.row == 0 || size.column == 0 {
+        if size.row == 0 || size.column == 0 || size.row.checked_mul(size.column).is_none() {
             return Err(SolverError::InvalidBoardSize(size.column, size.row));
         }
 
         display.change_state(SolverState::Loading("Validating hints.".to_string()));
 
+        Self::validate_hint_count(size.row, &row_hint, LineDirection::Row)?;
+        Self::validate_hint_count(size.column, &column_hint, LineDirection::Column)?;
         Self::validate_hints(size.column, &row_hint, LineDirection::Row)?;
         Self::validate_hints(size.row, &column_hint, LineDirection::Column)?;
+        display.set_hints(&row_hint, &column_hint);
 
         let board = Board::new(size, Cell::Unknown);
 
@@ -78,152 +147,338 @@ impl Solver {
         display.change_state(SolverState::Loading(
             "Calculating initial possibilities.".to_string(),
         ));
-        let possibilities = [(&row_hint, size.column), (&column_hint, size.row)]
-            .into_iter()
-            .flat_map(|(hints, size)| hints.iter().map(move |hint| (hint, size)))
-            .map(|(hint, size)| calculator.calc_distribute_count_line_hint(hint, size))
-            .map(|count| (0..count).collect::<BitSet>())
-            .collect::<Vec<_>>();
+        let possibilities = [
+            (&row_hint, size.column, LineDirection::Row),
+            (&column_hint, size.row, LineDirection::Column),
+        ]
+        .into_iter()
+        .flat_map(|(hints, size, direction)| {
+            hints
+                .iter()
+                .enumerate()
+                .map(move |(idx, hint)| (hint, size, Line::new(direction, idx)))
+        })
+        .map(|(hint, size, line)| {
+            let count = calculator.calc_distribute_count_line_hint(hint, size);
+            // 경우의 수가 usize를 넘치면 셀 수 없다
+            if count == usize::MAX {
+                return Err(SolverError::InvalidInitialInfo(InvalidInfoError {
+                    error_line: line,
+                    size,
+                    message: "Invalid hint: too many possible placements".to_string(),
+                }));
+            }
+            Ok(PlacementSet::full(count))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
         let given_hint = row_hint.into_iter().chain(column_hint).collect::<Vec<_>>();
 
-        let possibility_count = possibilities.iter().map(BitSet::len).collect();
-
-        let line_changed = [
+        let lines = [
             (0..size.row, LineDirection::Row),
             (0..size.column, LineDirection::Column),
         ]
         .into_iter()
         .flat_map(|(range, direction)| range.map(move |index| Line::new(direction, index)))
-        .collect::<HashSet<_>>();
+        .collect::<Vec<_>>();
+        let changed_cells = vec![0; lines.len()];
+        let line_bits = lines
+            .iter()
+            .map(|line| match line.direction() {
+                LineDirection::Row => BitLine::new(size.column),
+                LineDirection::Column => BitLine::new(size.row),
+            })
+            .collect();
 
         display.change_state(SolverState::Idle);
 
-        Ok(Self {
-            line_changed,
+        let mut solver = Self {
+            fill_ratio_cache: vec![None; lines.len()],
+            changed_cells,
+            line_queue: LineQueue::new(lines.clone()),
+            heuristic: LineHeuristic::default(),
+            level: SolveLevel::default(),
+            speculation_depth: 0,
             display,
             calculator,
             board,
             possibilities,
-            possibility_count,
+            line_bits,
             given_hint,
+            line_cache: None,
+            budget: SolveBudget::default(),
+            checkpoint_schedule: None,
+            history: History::default(),
+        };
+        for line in lines {
+            solver.queue_line(line);
+        }
+        Ok(solver)
+    }
+
+    /// Changes how the next line to solve is chosen. Lines already queued are reordered.
+    pub fn set_line_heuristic(&mut self, heuristic: LineHeuristic) {
+        self.heuristic = heuristic;
+        let queued = self.line_queue.queued().collect::<Vec<_>>();
+        for index in queued {
+            self.line_queue.remove(index);
+            let line = self.index_to_line(index);
+            self.queue_line(line);
+        }
+    }
+
+    pub fn line_heuristic(&self) -> LineHeuristic {
+        self.heuristic
+    }
+
+    /// Remembers up to `capacity` deduced lines, so that a line with the same hint and cells as
+    /// one solved before is not enumerated again. `0` turns the cache off.
+    ///
+    /// A cached deduction does not prune the placements of the line, so
+    /// [`LineHeuristic::FewestPossibilities`] sees fewer removals.
+    pub fn set_line_cache_capacity(&mut self, capacity: usize) {
+        self.line_cache = NonZeroUsize::new(capacity).map(LineCache::new);
+    }
+
+    pub fn line_cache_stats(&self) -> Option<LineCacheStats> {
+        self.line_cache.as_ref().map(LineCache::stats)
+    }
+
+    pub fn set_limits(&mut self, limits: SolveLimits) {
+        self.budget.limits = limits;
+    }
+
+    pub fn set_cancellation_token(&mut self, token: CancellationToken) {
+        self.budget.cancellation_token = token;
+    }
+
+    /// Token that aborts [`Solver::solve`] when cancelled from another thread.
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.budget.cancellation_token.clone()
+    }
+
+    pub fn line_passes(&self) -> usize {
+        self.budget.line_passes
+    }
+
+    pub fn placements(&self) -> usize {
+        self.budget.placements.load(Ordering::Relaxed)
+    }
+
+    fn aborted_error(&self, reason: AbortReason) -> SolverError {
+        SolverError::Aborted(AbortedError {
+            reason,
+            board: self.board.clone(),
+            checkpoint_error: None,
         })
     }
 
+    /// Use [`Solver::set_cell`] to change cells.
+    pub fn board(&self) -> &Board<Cell> {
+        &self.board
+    }
+
     pub fn is_solved(&self) -> bool {
-        self.board.iter_all().all(|cell| *cell != Cell::Unknown)
+        self.line_bits[..self.board.size().row]
+            .iter()
+            .all(BitLine::is_complete)
     }
 
-    fn solve_line(&mut self, line: Line) -> Result<(), SolverError> {
-        let current_line = self.get_line_cells(line);
-        if current_line.iter().all(|&cell| cell != Cell::Unknown) {
-            return Ok(());
+    /// Sets a cell and queues the lines crossing it.
+    pub fn set_cell(&mut self, position: Vec2, cell: Cell) {
+        if *self.board.value(position) == cell {
+            return;
         }
+        self.change_cell(position, cell, None);
+        self.mark_line_changed(Line::new(LineDirection::Row, position.row));
+        self.mark_line_changed(Line::new(LineDirection::Column, position.column));
+    }
 
-        let line_length = current_line.len();
-        let mapped_line_index = self.line_to_index(line);
-        let hint = &self.given_hint[mapped_line_index];
-        let possibilities = self.possibilities[mapped_line_index]
-            .iter()
-            .collect::<Vec<_>>();
-        let total_possibilities = possibilities.len();
+    fn write_cell(&mut self, position: Vec2, cell: Cell) {
+        let column_index = self.board.size().row + position.column;
+        *self.board.value_mut(position) = cell;
+        self.line_bits[position.row].set(position.column, cell);
+        self.line_bits[column_index].set(position.row, cell);
+        self.fill_ratio_cache[position.row] = None;
+        self.fill_ratio_cache[column_index] = None;
+    }
 
-        let mut new_line = vec![Cell::Unknown; line_length];
-        let mut indexed_line = Vec::new();
+    fn cached_deduction(&mut self, line: Line) -> Option<LineDeduction> {
+        let index = self.line_to_index(line);
+        let new_line = self
+            .line_cache
+            .as_mut()?
+            .get(&self.given_hint[index], &self.line_bits[index])?;
+        Some(LineDeduction {
+            new_line,
+            removed: Vec::new(),
+        })
+    }
 
-        if hint.is_empty() {
-            let new_line = vec![Cell::Blank; line_length];
-            self.update_line(line, &new_line);
-            return Ok(());
+    /// Must be called before the deduction is applied, while the line still has its old cells.
+    fn cache_deduction(&mut self, line: Line, result: &Result<LineDeduction, DeduceError>) {
+        let index = self.line_to_index(line);
+        if let (Some(cache), Ok(deduction)) = (self.line_cache.as_mut(), result) {
+            cache.insert(
+                &self.given_hint[index],
+                self.line_bits[index].clone(),
+                deduction.new_line.clone(),
+            );
         }
+    }
 
-        for (i, possibility_index) in possibilities.into_iter().enumerate() {
-            self.display.update_progress((i + 1, total_possibilities));
-
-            self.calculator
-                .calc_distribute_number_line_hint(
-                    hint,
-                    line_length,
-                    possibility_index,
-                    &mut indexed_line,
-                )
-                .map_err(|e| {
-                    SolverError::InvalidSolvingState(SolvingError {
-                        current_line: current_line.clone(),
-                        calculating_line: indexed_line.clone(),
-                        hint: hint.clone(),
-                        error_line: line,
-                        message: e,
-                    })
-                })?;
-
-            if indexed_line
-                .iter()
-                .zip(current_line.iter())
-                .any(|(indexed_cell, cell)| (*indexed_cell | *cell) == Cell::Crash)
-            {
-                self.possibilities[mapped_line_index].remove(possibility_index);
-                continue;
-            }
+    fn solve_line(&mut self, line: Line) -> Result<(), SolverError> {
+        if let Some(deduction) = self.cached_deduction(line) {
+            return self.apply_deduction(line, Ok(deduction));
+        }
+        let mapped_line_index = self.line_to_index(line);
 
-            new_line
-                .iter_mut()
-                .zip(indexed_line.iter())
-                .for_each(|(cell, &indexed_cell)| {
-                    *cell = *cell | indexed_cell;
-                });
+        let display = &mut self.display;
+        let budget = &self.budget;
+        let result = deduce_line(
+            &mut self.calculator,
+            line,
+            &self.given_hint[mapped_line_index],
+            &self.line_bits[mapped_line_index],
+            &self.possibilities[mapped_line_index],
+            |i, total| {
+                display.update_progress((i + 1, total));
+                budget.count_placement(i)
+            },
+        );
+
+        self.cache_deduction(line, &result);
+        self.apply_deduction(line, result)
+    }
 
-            if new_line
-                .iter()
-                .zip(current_line.iter())
-                .all(|(new_cell, line_cell)| {
-                    *line_cell != Cell::Unknown && *new_cell == Cell::Crash
-                })
-            {
-                break;
+    fn apply_deduction(
+        &mut self,
+        line: Line,
+        result: Result<LineDeduction, DeduceError>,
+    ) -> Result<(), SolverError> {
+        match result {
+            Ok(deduction) => {
+                self.remove_placements(line, deduction.removed);
+                self.update_line(line, &deduction.new_line);
+                Ok(())
             }
+            Err(DeduceError::Aborted(reason)) => {
+                // 다시 풀 수 있도록 큐에 되돌려 놓는다
+                self.queue_line(line);
+                Err(self.aborted_error(reason))
+            }
+            Err(DeduceError::Contradiction) => Err(SolverError::Contradiction(line)),
+            Err(DeduceError::Invalid(e)) => Err(SolverError::InvalidSolvingState(e)),
         }
+    }
 
-        self.update_line(line, &new_line);
+    /// Solves the next queued line. Returns `None` when no line is left to solve.
+    pub fn solve_step(&mut self) -> Result<Option<Line>, SolverError> {
+        let Some(line) = self.next_line_pop() else {
+            return Ok(None);
+        };
 
-        Ok(())
+        if let Err(reason) = self.budget.count_line_pass() {
+            self.queue_line(line);
+            return Err(self.aborted_error(reason));
+        }
+
+        let heatmap = self.display.wants_heatmap().then(|| self.heatmap());
+        let line_waiting = if self.display.wants_waiting_lines() {
+            self.line_queue.first(WAITING_LINES)
+        } else {
+            Vec::new()
+        };
+        self.display
+            .change_state(SolverState::Solving(SolvingContext {
+                board: self.board.clone(),
+                line,
+                line_waiting,
+                queue_length: self.line_queue.len(),
+                heatmap,
+            }));
+        self.solve_line(line)?;
+        self.save_checkpoint(false)?;
+
+        Ok(Some(line))
     }
 
+    /// Solves with line logic, then goes on as far as [`Solver::set_solve_level`] allows.
     pub fn solve(&mut self) -> Result<(), SolverError> {
-        while let Some(line) = self.next_line_pop() {
-            self.display
-                .change_state(SolverState::Solving(SolvingContext {
-                    board: self.board.clone(),
-                    line,
-                    line_waiting: self.line_order(),
-                }));
-            self.solve_line(line)?;
-        }
+        let result = self.solve_lines().and_then(|()| self.solve_beyond_lines());
+        self.finish_solving(result)
+    }
 
-        self.display.change_state(SolverState::Solved);
+    /// Solves queued lines until none is left.
+    fn solve_lines(&mut self) -> Result<(), SolverError> {
+        while self.solve_step()?.is_some() {}
         Ok(())
     }
 
-    fn get_line_sort_key(&self, line: Line) -> usize {
-        self.possibility_count[self.line_to_index(line)]
+    fn finish_solving(&mut self, mut result: Result<(), SolverError>) -> Result<(), SolverError> {
+        match &mut result {
+            Ok(()) => self.display.change_state(SolverState::Solved),
+            Err(SolverError::Aborted(e)) => {
+                self.display.change_state(SolverState::Aborted(e.reason));
+                // 중단된 지점부터 다시 풀 수 있도록 저장한다
+                if let Err(save_error) = self.save_checkpoint(true) {
+                    e.checkpoint_error = Some(save_error.to_string());
+                }
+            }
+            Err(_) => {}
+        }
+        result
     }
 
-    fn next_line(&self) -> Option<Line> {
-        self.line_changed
-            .iter()
-            .min_by_key(|line| self.get_line_sort_key(**line))
-            .cloned()
+    fn index_to_line(&self, index: usize) -> Line {
+        if index < self.board.size().row {
+            Line::new(LineDirection::Row, index)
+        } else {
+            Line::new(LineDirection::Column, index - self.board.size().row)
+        }
+    }
+
+    fn line_stats(&self, line: Line) -> LineStats {
+        let index = self.line_to_index(line);
+        LineStats {
+            possibilities: self.possibilities[index].len(),
+            unknown_cells: self.line_bits[index].unknown_count(),
+            changed_cells: self.changed_cells[index],
+        }
+    }
+
+    /// Queues `line` to be solved, or moves it if the heuristic ranks it differently now.
+    fn queue_line(&mut self, line: Line) {
+        let index = self.line_to_index(line);
+        if self.line_queue.contains(index) && !self.heuristic.requeues() {
+            return;
+        }
+        let cost = self
+            .heuristic
+            .cost(&self.line_stats(line), self.line_queue.tick());
+        self.line_queue.push(index, cost);
+    }
+
+    /// Called when a cell of `line` changed.
+    fn mark_line_changed(&mut self, line: Line) {
+        let index = self.line_to_index(line);
+        self.changed_cells[index] += 1;
+        self.queue_line(line);
+    }
+
+    fn clear_line_queue(&mut self) {
+        self.line_queue.clear();
+        self.changed_cells.iter_mut().for_each(|count| *count = 0);
     }
 
     fn next_line_pop(&mut self) -> Option<Line> {
-        let line = self.next_line()?;
-        self.line_changed.remove(&line);
+        let (index, line) = self.line_queue.pop()?;
+        self.changed_cells[index] = 0;
         Some(line)
     }
 
+    #[cfg_attr(not(feature = "parallel"), allow(dead_code))]
     fn line_order(&self) -> Vec<Line> {
-        let mut order = self.line_changed.iter().cloned().collect::<Vec<_>>();
-        order.sort_by_key(|line| self.get_line_sort_key(*line));
-        order
+        self.line_queue.ordered()
     }
 }
 
@@ -231,9 +486,12 @@ impl Solver {
 mod test {
     use super::*;
     use crate::{
-        display::SimpleConsoleDisplay,
+        display::{NullDisplay, SimpleConsoleDisplay},
+        generator::derive_hints,
         solver::parser::{FileSolverParser, SolverParser},
     };
+    use proptest::prelude::*;
+    use std::time::Instant;
 
     #[test]
     fn test_solve() {
@@ -245,4 +503,217 @@ mod test {
 
         assert!(solver.is_solved());
     }
+
+    fn create_solver() -> Solver {
+        FileSolverParser::new("./sample/data1.txt")
+            .create_solver(Box::new(SimpleConsoleDisplay::new_with_default()))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_line_pass_limit() {
+        let mut solver = create_solver();
+        solver.set_limits(SolveLimits {
+            max_line_passes: Some(3),
+            ..Default::default()
+        });
+
+        let Err(SolverError::Aborted(e)) = solver.solve() else {
+            panic!("Expected the solver to abort");
+        };
+        assert_eq!(e.reason, AbortReason::LinePassLimit);
+        assert_eq!(solver.line_passes(), 3);
+        assert!(e.board.iter_all().any(|&cell| cell != Cell::Unknown));
+
+        solver.set_limits(SolveLimits::default());
+        solver.solve().unwrap();
+        assert!(solver.is_solved());
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut solver = create_solver();
+        solver.cancellation_token().cancel();
+
+        let Err(SolverError::Aborted(e)) = solver.solve() else {
+            panic!("Expected the solver to abort");
+        };
+        assert_eq!(e.reason, AbortReason::Cancelled);
+    }
+
+    #[test]
+    fn test_deadline() {
+        let mut solver = create_solver();
+        solver.set_limits(SolveLimits {
+            deadline: Some(Instant::now()),
+            ..Default::default()
+        });
+
+        let Err(SolverError::Aborted(e)) = solver.solve() else {
+            panic!("Expected the solver to abort");
+        };
+        assert_eq!(e.reason, AbortReason::Timeout);
+    }
+
+    #[test]
+    fn test_contradiction() {
+        // 행 힌트는 두 칸을, 열 힌트는 한 칸만 채우라고 한다
+        let mut solver = Solver::new(
+            Vec2::new(2, 2),
+            vec![vec![2], vec![]],
+            vec![vec![], vec![1]],
+            Box::new(SimpleConsoleDisplay::new_with_default()),
+        )
+        .unwrap();
+
+        assert!(matches!(solver.solve(), Err(SolverError::Contradiction(_))));
+    }
+
+    #[test]
+    fn test_contradiction_in_empty_column() {
+        let mut solver = Solver::new(
+            Vec2::new(3, 2),
+            vec![vec![2], vec![], vec![]],
+            vec![vec![], vec![1]],
+            Box::new(NullDisplay),
+        )
+        .unwrap();
+        solver.set_line_heuristic(LineHeuristic::InformationGain);
+
+        assert!(matches!(solver.solve(), Err(SolverError::Contradiction(_))));
+    }
+
+    #[test]
+    fn test_placement_limit() {
+        let mut solver = create_solver();
+        solver.set_limits(SolveLimits {
+            max_placements: Some(10),
+            ..Default::default()
+        });
+
+        let Err(SolverError::Aborted(e)) = solver.solve() else {
+            panic!("Expected the solver to abort");
+        };
+        assert_eq!(e.reason, AbortReason::PlacementLimit);
+        assert_eq!(solver.placements(), 10);
+    }
+
+    fn new_solver(
+        size: Vec2,
+        row_hint: Vec<Vec<usize>>,
+        column_hint: Vec<Vec<usize>>,
+    ) -> Result<Solver, SolverError> {
+        Solver::new(size, row_hint, column_hint, Box::new(NullDisplay))
+    }
+
+    #[test]
+    fn test_invalid_hints() {
+        // 힌트 수가 보드 크기와 다르다
+        assert!(matches!(
+            new_solver(Vec2::new(2, 1), vec![vec![1]], vec![vec![1]]),
+            Err(SolverError::InvalidInitialInfo(_))
+        ));
+        // 힌트 합이 넘친다
+        assert!(matches!(
+            new_solver(
+                Vec2::new(1, 2),
+                vec![vec![usize::MAX, 1]],
+                vec![vec![], vec![]]
+            ),
+            Err(SolverError::InvalidInitialInfo(_))
+        ));
+        assert!(matches!(
+            new_solver(Vec2::new(usize::MAX, 2), vec![], vec![]),
+            Err(SolverError::InvalidBoardSize(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_line_heuristics() {
+        let heuristics = [
+            LineHeuristic::FewestPossibilities,
+            LineHeuristic::MostUnknownCells,
+            LineHeuristic::MostRecentlyChanged,
+            LineHeuristic::RoundRobin,
+            LineHeuristic::InformationGain,
+        ];
+        let mut expected = create_solver();
+        expected.solve().unwrap();
+
+        for heuristic in heuristics {
+            let mut solver = create_solver();
+            solver.set_line_heuristic(heuristic);
+            assert_eq!(solver.line_order().len(), 20);
+            solver.solve().unwrap();
+            assert!(solver.is_solved(), "{} did not solve", heuristic);
+            assert!(solver.board().iter_all().eq(expected.board().iter_all()));
+        }
+    }
+
+    #[test]
+    fn test_round_robin_order() {
+        let mut solver = create_solver();
+        solver.set_line_heuristic(LineHeuristic::RoundRobin);
+        let first = solver.line_order();
+        assert_eq!(first[0], Line::new(LineDirection::Row, 0));
+        assert_eq!(first[10], Line::new(LineDirection::Column, 0));
+        assert_eq!(solver.solve_step().unwrap(), Some(first[0]));
+    }
+
+    #[test]
+    fn test_line_cache() {
+        let mut expected = create_solver();
+        expected.solve().unwrap();
+        assert_eq!(expected.line_cache_stats(), None);
+
+        let mut solver = create_solver();
+        solver.set_line_cache_capacity(64);
+        solver.solve().unwrap();
+        assert!(solver.board().iter_all().eq(expected.board().iter_all()));
+
+        // 같은 상태로 다시 풀면 모두 캐시에서 나온다
+        let stats = solver.line_cache_stats().unwrap();
+        assert_eq!(stats.hits + stats.misses, solver.line_passes());
+        let mut again = create_solver();
+        again.line_cache = solver.line_cache.take();
+        again.solve().unwrap();
+        assert!(again.is_solved());
+        let again_stats = again.line_cache_stats().unwrap();
+        assert!(again_stats.hits > stats.hits);
+        assert!(again.placements() < solver.placements());
+    }
+
+    fn board_strategy() -> impl Strategy<Value = Board<Cell>> {
+        (1usize..=8, 1usize..=8).prop_flat_map(|(row, column)| {
+            prop::collection::vec(any::<bool>(), row * column).prop_map(move |cells| {
+                let mut board = Board::new(Vec2::new(row, column), Cell::Blank);
+                for (index, filled) in cells.into_iter().enumerate() {
+                    if filled {
+                        *board.value_mut(Vec2::new(index / column, index % column)) = Cell::Block;
+                    }
+                }
+                board
+            })
+        })
+    }
+
+    proptest! {
+        // 라인 논리로 정한 칸은 모든 해에서 같으므로 원본 보드와 같아야 한다
+        #[test]
+        fn test_decided_cells_match_source(board in board_strategy()) {
+            let hints = derive_hints(&board);
+            let mut solver = Solver::new(
+                hints.board_size,
+                hints.row_hints,
+                hints.column_hints,
+                Box::new(NullDisplay),
+            )
+            .unwrap();
+            solver.solve().unwrap();
+
+            for (solved, source) in solver.board().iter_all().zip(board.iter_all()) {
+                prop_assert!(*solved == Cell::Unknown || solved == source);
+            }
+        }
+    }
 }
@@ -0,0 +1,182 @@
+use std::cell::RefCell;
+
+use rayon::prelude::*;
+
+use super::calculator::NumberDistributionCalculator;
+use super::error::SolverError;
+use super::line_solver::deduce_line;
+use super::solver_display::{SolverState, SolvingContext, WAITING_LINES};
+use super::types::{LineDirection, LineSolvingInfoProvider};
+use super::Solver;
+
+thread_local! {
+    /// Kept for the life of each pool thread, so its cache outlives a batch.
+    static CALCULATOR: RefCell<NumberDistributionCalculator> =
+        RefCell::new(NumberDistributionCalculator::new());
+}
+
+impl Solver {
+    /// Like [`Solver::solve`], but deduces every queued line of one direction at once on a
+    /// thread pool, then switches direction.
+    ///
+    /// Levels above line logic run on one thread.
+    ///
+    /// Lines of the same direction share no cells, so their results can be merged in any order.
+    /// Each pool thread keeps its own [`NumberDistributionCalculator`] across batches.
+    pub fn solve_parallel(&mut self) -> Result<(), SolverError> {
+        let mut direction = self
+            .line_queue
+            .peek()
+            .map_or(LineDirection::Row, |line| line.direction());
+
+        let result = loop {
+            match self.solve_batch(direction) {
+                Ok(true) => direction = direction.opposite(),
+                Ok(false) if self.line_queue.is_empty() => break Ok(()),
+                Ok(false) => direction = direction.opposite(),
+                Err(e) => break Err(e),
+            }
+        };
+
+        let result = result.and_then(|()| self.solve_beyond_lines());
+        self.finish_solving(result)
+    }
+
+    /// Solves every queued line of `direction`. Returns `false` if there was none.
+    fn solve_batch(&mut self, direction: LineDirection) -> Result<bool, SolverError> {
+        let mut batch = self
+            .line_order()
+            .into_iter()
+            .filter(|line| line.direction() == direction)
+            .collect::<Vec<_>>();
+        if batch.is_empty() {
+            return Ok(false);
+        }
+
+        let mut pass_limit = None;
+        let mut allowed = 0;
+        for line in &batch {
+            if let Err(reason) = self.budget.count_line_pass() {
+                pass_limit = Some(reason);
+                break;
+            }
+            let index = self.line_to_index(*line);
+            self.line_queue.remove(index);
+            self.changed_cells[index] = 0;
+            allowed += 1;
+        }
+        batch.truncate(allowed);
+
+        if let Some(&line) = batch.first() {
+            let heatmap = self.display.wants_heatmap().then(|| self.heatmap());
+            let line_waiting = if self.display.wants_waiting_lines() {
+                self.line_queue.first(WAITING_LINES)
+            } else {
+                Vec::new()
+            };
+            self.display
+                .change_state(SolverState::Solving(SolvingContext {
+                    board: self.board.clone(),
+                    line,
+                    line_waiting,
+                    queue_length: self.line_queue.len(),
+                    heatmap,
+                }));
+        }
+
+        let mut cached = Vec::new();
+        let mut jobs = Vec::new();
+        for &line in &batch {
+            match self.cached_deduction(line) {
+                Some(deduction) => cached.push((line, deduction)),
+                None => jobs.push((line, self.line_to_index(line))),
+            }
+        }
+
+        let budget = &self.budget;
+        let hints = &self.given_hint;
+        let possibilities = &self.possibilities;
+        let line_bits = &self.line_bits;
+        let results = jobs
+            .par_iter()
+            .map(|(line, index)| {
+                CALCULATOR.with_borrow_mut(|calculator| {
+                    deduce_line(
+                        calculator,
+                        *line,
+                        &hints[*index],
+                        &line_bits[*index],
+                        &possibilities[*index],
+                        |i, _| budget.count_placement(i),
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut error = None;
+        let cached = cached
+            .into_iter()
+            .map(|(line, deduction)| (line, Ok(deduction)));
+        let solved = jobs
+            .into_iter()
+            .zip(results)
+            .map(|((line, _), result)| {
+                self.cache_deduction(line, &result);
+                (line, result)
+            })
+            .collect::<Vec<_>>();
+        for (line, result) in cached.chain(solved) {
+            if let Err(e) = self.apply_deduction(line, result) {
+                error.get_or_insert(e);
+            }
+        }
+
+        // 모든 결과를 합친 뒤의 보드를 돌려준다
+        match (error, pass_limit) {
+            (Some(SolverError::Aborted(e)), _) => Err(self.aborted_error(e.reason)),
+            (Some(e), _) => Err(e),
+            (None, Some(reason)) => Err(self.aborted_error(reason)),
+            (None, None) => self.save_checkpoint(false).map(|_| true),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        display::SimpleConsoleDisplay,
+        solver::parser::{FileSolverParser, HtmlTableSolverParser, SolverParser},
+    };
+
+    #[test]
+    fn test_solve_parallel_matches_serial() {
+        let html = include_str!("../../sample/table/data2.txt");
+        let parsers: [Box<dyn SolverParser>; 2] = [
+            Box::new(FileSolverParser::new("./sample/data1.txt")),
+            Box::new(HtmlTableSolverParser::new(html)),
+        ];
+
+        for parser in parsers {
+            let mut serial = parser
+                .create_solver(Box::new(SimpleConsoleDisplay::new_with_default()))
+                .unwrap();
+            serial.solve().unwrap();
+
+            let mut parallel = parser
+                .create_solver(Box::new(SimpleConsoleDisplay::new_with_default()))
+                .unwrap();
+            parallel.solve_parallel().unwrap();
+
+            assert!(parallel.is_solved());
+            assert!(parallel.board().iter_all().eq(serial.board().iter_all()));
+
+            let mut cached = parser
+                .create_solver(Box::new(SimpleConsoleDisplay::new_with_default()))
+                .unwrap();
+            cached.set_line_cache_capacity(1024);
+            cached.solve_parallel().unwrap();
+            assert!(cached.board().iter_all().eq(serial.board().iter_all()));
+            assert!(cached.line_cache_stats().unwrap().misses > 0);
+        }
+    }
+}
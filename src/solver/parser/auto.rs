@@ -0,0 +1,43 @@
+use super::{HtmlTableSolverParser, SolverParseResult, SolverParser, TextSolverParser};
+
+/// Picks the parser matching the content: an HTML table or the plain text format.
+pub struct AutoSolverParser<'a> {
+    content: &'a str,
+}
+
+impl<'a> AutoSolverParser<'a> {
+    pub fn new(content: &'a str) -> Self {
+        Self { content }
+    }
+
+    pub fn is_html(&self) -> bool {
+        self.content.trim_start().starts_with('<') || self.content.contains("<table")
+    }
+}
+
+impl SolverParser for AutoSolverParser<'_> {
+    fn parse(&self) -> Result<SolverParseResult, String> {
+        if self.is_html() {
+            HtmlTableSolverParser::new(self.content).parse()
+        } else {
+            TextSolverParser::new(self.content).parse()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_format() {
+        let text = include_str!("../../../sample/data1.txt");
+        let html = include_str!("../../../sample/table/data1.txt");
+        assert!(!AutoSolverParser::new(text).is_html());
+        assert!(AutoSolverParser::new(html).is_html());
+
+        let text_result = AutoSolverParser::new(text).parse().unwrap();
+        assert_eq!(text_result.board_size.row, 10);
+        assert!(AutoSolverParser::new(html).parse().is_ok());
+    }
+}
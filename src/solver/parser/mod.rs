@@ -1,8 +1,12 @@
+mod auto;
 mod file;
 mod html;
+mod text;
 
+pub use auto::AutoSolverParser;
 pub use file::FileSolverParser;
 pub use html::HtmlTableSolverParser;
+pub use text::TextSolverParser;
 
 use crate::board::Vec2;
 use crate::solver::solver_display::SolverDisplay;
@@ -0,0 +1,125 @@
+use super::{SolverParseResult, SolverParser};
+use crate::board::Vec2;
+
+pub struct TextSolverParser<'a> {
+    content: &'a str,
+}
+
+impl<'a> TextSolverParser<'a> {
+    pub fn new(content: &'a str) -> Self {
+        Self { content }
+    }
+}
+
+impl SolverParser for TextSolverParser<'_> {
+    fn parse(&self) -> Result<SolverParseResult, String> {
+        let mut lines = self.content.lines();
+
+        // 첫 번째 줄: 행의 수와 열의 수
+        let first_line = lines.next().ok_or_else(|| "File is empty.".to_string())?;
+
+        let first_line = first_line.trim_start_matches('\u{FEFF}'); // BOM 제거
+
+        let mut dimensions = first_line.split_whitespace();
+        let row_count: usize = dimensions
+            .next()
+            .ok_or_else(|| "Row count not found.".to_string())?
+            .parse()
+            .map_err(|_| "Failed to parse row count.".to_string())?;
+        let column_count: usize = dimensions
+            .next()
+            .ok_or_else(|| "Column count not found.".to_string())?
+            .parse()
+            .map_err(|_| "Failed to parse column count.".to_string())?;
+
+        // 행 힌트
+        let mut row_hints = Vec::new();
+        for _ in 0..row_count {
+            let hint_line = lines
+                .next()
+                .ok_or_else(|| "Not enough row hints.".to_string())?;
+            let hints = hint_line
+                .split_whitespace()
+                .map(|s| {
+                    s.parse()
+                        .map_err(|_| "Failed to parse row hint.".to_string())
+                })
+                .collect::<Result<Vec<usize>, _>>()?;
+            row_hints.push(hints);
+        }
+
+        // 열 힌트
+        let mut column_hints = Vec::new();
+        for _ in 0..column_count {
+            let hint_line = lines
+                .next()
+                .ok_or_else(|| "Not enough column hints.".to_string())?;
+            let hints = hint_line
+                .split_whitespace()
+                .map(|s| {
+                    s.parse()
+                        .map_err(|_| "Failed to parse column hint.".to_string())
+                })
+                .collect::<Result<Vec<usize>, _>>()?;
+            column_hints.push(hints);
+        }
+
+        Ok(SolverParseResult {
+            board_size: Vec2 {
+                row: row_count,
+                column: column_count,
+            },
+            row_hints,
+            column_hints,
+        })
+    }
+}
+
+impl SolverParseResult {
+    /// Formats the puzzle in the format read by [`TextSolverParser`].
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{} {}\n", self.board_size.row, self.board_size.column);
+        for hint in self.row_hints.iter().chain(self.column_hints.iter()) {
+            let numbers = hint.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+            text.push_str(&numbers.join(" "));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_text() {
+        let result = TextSolverParser::new("2 3\n1 1\n3\n2\n1\n2\n")
+            .parse()
+            .unwrap();
+        assert_eq!(result.board_size, Vec2::new(2, 3));
+        assert_eq!(result.row_hints, vec![vec![1, 1], vec![3]]);
+        assert_eq!(result.column_hints, vec![vec![2], vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn test_to_text_round_trip() {
+        let content = include_str!("../../../sample/data2.txt");
+        let result = TextSolverParser::new(content).parse().unwrap();
+        let reparsed = TextSolverParser::new(&result.to_text()).parse().unwrap();
+        assert_eq!(reparsed.row_hints, result.row_hints);
+        assert_eq!(reparsed.column_hints, result.column_hints);
+    }
+
+    #[test]
+    fn test_huge_dimensions() {
+        let result = TextSolverParser::new("18446744073709551615 1\n1\n").parse();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_not_enough_hints() {
+        let result = TextSolverParser::new("2 2\n1\n").parse();
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use std::ops::Range;
+
+/// Indices of the placements of a line that are still possible, as sorted, disjoint ranges.
+///
+/// Neighbouring placements tend to be removed together, so a line keeps a few ranges instead of
+/// one bit per placement, and a new line is a single range however many placements it has.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) struct PlacementSet {
+    ranges: Vec<Range<usize>>,
+    len: usize,
+}
+
+impl PlacementSet {
+    /// Every placement in `0..count`.
+    pub fn full(count: usize) -> Self {
+        Self {
+            ranges: (count > 0).then_some(0..count).into_iter().collect(),
+            len: count,
+        }
+    }
+
+    /// Ranges from `0..count`, or `None` unless they are sorted, disjoint and non-empty.
+    pub fn from_ranges(ranges: Vec<Range<usize>>, count: usize) -> Option<Self> {
+        let valid = ranges
+            .iter()
+            .all(|range| range.start < range.end && range.end <= count)
+            && ranges.windows(2).all(|pair| pair[0].end < pair[1].start);
+        valid.then(|| Self {
+            len: ranges.iter().map(Range::len).sum(),
+            ranges,
+        })
+    }
+
+    pub fn ranges(&self) -> &[Range<usize>] {
+        &self.ranges
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.ranges.iter().flat_map(Range::clone)
+    }
+
+    /// Removes `removed`, which must be sorted and made of placements in this set.
+    pub fn remove(&mut self, removed: &[Range<usize>]) {
+        let mut removed = removed.iter().peekable();
+        let mut ranges = Vec::with_capacity(self.ranges.len());
+        for range in self.ranges.drain(..) {
+            let mut start = range.start;
+            while let Some(gap) = removed.next_if(|gap| gap.start < range.end) {
+                if start < gap.start {
+                    ranges.push(start..gap.start);
+                }
+                start = gap.end;
+                self.len -= gap.len();
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+        self.ranges = ranges;
+    }
+
+    /// Adds `added` back, which must be sorted and made of placements not in this set.
+    pub fn insert(&mut self, added: &[Range<usize>]) {
+        self.len += added.iter().map(Range::len).sum::<usize>();
+        let mut merged = std::mem::take(&mut self.ranges);
+        merged.extend(added.iter().cloned());
+        merged.sort_unstable_by_key(|range| range.start);
+        for range in merged {
+            // 맞닿은 구간은 하나로 합친다
+            match self.ranges.last_mut() {
+                Some(last) if last.end == range.start => last.end = range.end,
+                _ => self.ranges.push(range),
+            }
+        }
+    }
+}
+
+/// Adds `index` to sorted `ranges`, extending the last range if it ends right before `index`.
+pub(crate) fn push_index(ranges: &mut Vec<Range<usize>>, index: usize) {
+    match ranges.last_mut() {
+        Some(last) if last.end == index => last.end += 1,
+        _ => ranges.push(index..index + 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove() {
+        let mut set = PlacementSet::full(10);
+        set.remove(&[0..2, 4..5]);
+        assert_eq!(set.len(), 7);
+        set.remove(&[3..4, 8..10]);
+        assert_eq!(set.len(), 4);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![2, 5, 6, 7]);
+        assert_eq!(set.ranges, vec![2..3, 5..8]);
+
+        set.remove(&[2..3, 5..8]);
+        assert_eq!(set.len(), 0);
+        assert_eq!(set.iter().next(), None);
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut set = PlacementSet::full(10);
+        set.remove(&[0..2, 4..5, 8..10]);
+        set.insert(&[4..5, 8..9]);
+        assert_eq!(set.ranges, vec![2..9]);
+        assert_eq!(set.len(), 7);
+        set.insert(&[0..2, 9..10]);
+        assert_eq!(set, PlacementSet::full(10));
+    }
+
+    #[test]
+    fn test_from_ranges() {
+        let set = PlacementSet::from_ranges(vec![1..3, 5..6], 6).unwrap();
+        assert_eq!(set.len(), 3);
+        assert!(PlacementSet::from_ranges(vec![1..3, 3..6], 6).is_none());
+        assert!(PlacementSet::from_ranges(vec![0..1, 2..7], 6).is_none());
+        assert!(PlacementSet::from_ranges(vec![0..1, 2..2], 6).is_none());
+    }
+
+    #[test]
+    fn test_push_index() {
+        let mut ranges = Vec::new();
+        for index in [1, 2, 3, 5, 7, 8] {
+            push_index(&mut ranges, index);
+        }
+        assert_eq!(ranges, vec![1..4, 5..6, 7..9]);
+    }
+}
@@ -0,0 +1,291 @@
+use std::fmt::Display;
+
+use super::cell::Cell;
+use super::error::SolverError;
+use super::types::{Line, LineDirection};
+use super::Solver;
+use crate::board::{Board, Vec2};
+
+/// How far [`Solver::solve`] goes once line logic alone makes no more progress.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+pub enum SolveLevel {
+    /// Stop where line logic stops.
+    #[default]
+    Line,
+    /// Try each value of each unknown cell with line logic, and keep the other value when one
+    /// leads to a contradiction.
+    Probe,
+    /// Probe, then guess cells and backtrack until the board is solved.
+    Search,
+}
+
+impl SolveLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SolveLevel::Line => "line",
+            SolveLevel::Probe => "probe",
+            SolveLevel::Search => "search",
+        }
+    }
+}
+
+impl Display for SolveLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+fn opposite(cell: Cell) -> Cell {
+    match cell {
+        Cell::Block => Cell::Blank,
+        _ => Cell::Block,
+    }
+}
+
+impl Solver {
+    pub fn set_solve_level(&mut self, level: SolveLevel) {
+        self.level = level;
+    }
+
+    pub fn solve_level(&self) -> SolveLevel {
+        self.level
+    }
+
+    /// Runs the levels above line logic. Line logic must have finished.
+    pub(super) fn solve_beyond_lines(&mut self) -> Result<(), SolverError> {
+        match self.level {
+            SolveLevel::Line => Ok(()),
+            SolveLevel::Probe => self.probe(),
+            SolveLevel::Search => self.search(),
+        }
+    }
+
+    fn unknown_cells(&self) -> Vec<Vec2> {
+        let size = self.board.size();
+        (0..size.row)
+            .flat_map(|row| (0..size.column).map(move |column| Vec2::new(row, column)))
+            .filter(|&position| *self.board.value(position) == Cell::Unknown)
+            .collect()
+    }
+
+    /// Probes every unknown cell until no probe decides anything more.
+    fn probe(&mut self) -> Result<(), SolverError> {
+        loop {
+            let mut progress = false;
+            for position in self.unknown_cells() {
+                for cell in [Cell::Block, Cell::Blank] {
+                    if *self.board.value(position) != Cell::Unknown {
+                        break;
+                    }
+                    if self.contradicts(position, cell)? {
+                        self.set_cell(position, opposite(cell));
+                        self.solve_lines()?;
+                        progress = true;
+                    }
+                }
+            }
+            if !progress || self.is_solved() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Whether line logic finds a contradiction after setting `position` to `cell`. The board
+    /// is left as it was.
+    fn contradicts(&mut self, position: Vec2, cell: Cell) -> Result<bool, SolverError> {
+        let checkpoint = self.checkpoint();
+        self.speculation_depth += 1;
+        self.set_cell(position, cell);
+        let result = self.solve_lines();
+        self.speculation_depth -= 1;
+        self.undo_to(checkpoint);
+        self.release(checkpoint);
+        // 되돌린 상태는 이미 라인 논리로 더 풀 것이 없다
+        self.clear_line_queue();
+
+        match result {
+            Ok(()) => Ok(false),
+            Err(SolverError::Contradiction(_)) => Ok(true),
+            Err(SolverError::Aborted(e)) => Err(self.aborted_error(e.reason)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Probes, then guesses the first unknown cell and backtracks on contradictions.
+    fn search(&mut self) -> Result<(), SolverError> {
+        self.probe()?;
+        let Some(&position) = self.unknown_cells().first() else {
+            return Ok(());
+        };
+
+        for cell in [Cell::Block, Cell::Blank] {
+            let checkpoint = self.checkpoint();
+            self.speculation_depth += 1;
+            self.set_cell(position, cell);
+            let result = self.solve_lines().and_then(|()| self.search());
+            self.speculation_depth -= 1;
+            if result.is_err() {
+                // 추측한 칸은 돌려주지 않는다
+                self.undo_to(checkpoint);
+                self.clear_line_queue();
+            }
+            self.release(checkpoint);
+
+            match result {
+                Ok(()) => return Ok(()),
+                Err(SolverError::Contradiction(_)) => {}
+                Err(SolverError::Aborted(e)) => return Err(self.aborted_error(e.reason)),
+                Err(e) => return Err(e),
+            }
+        }
+        Err(SolverError::Contradiction(Line::new(
+            LineDirection::Row,
+            position.row,
+        )))
+    }
+
+    /// Finds up to `limit` solutions by line logic and guessing. Only what line logic decides
+    /// before the first guess stays on the board. No solution is not an error.
+    pub fn enumerate_solutions(&mut self, limit: usize) -> Result<Vec<Board<Cell>>, SolverError> {
+        let mut solutions = Vec::new();
+        let result = match self.solve_lines() {
+            Ok(()) => self.collect_solutions(limit, &mut solutions),
+            Err(SolverError::Contradiction(_)) => Ok(()),
+            Err(e) => Err(e),
+        };
+        self.finish_solving(result)?;
+        Ok(solutions)
+    }
+
+    fn collect_solutions(
+        &mut self,
+        limit: usize,
+        solutions: &mut Vec<Board<Cell>>,
+    ) -> Result<(), SolverError> {
+        let Some(&position) = self.unknown_cells().first() else {
+            if solutions.len() < limit {
+                solutions.push(self.board.clone());
+            }
+            return Ok(());
+        };
+
+        for cell in [Cell::Block, Cell::Blank] {
+            if solutions.len() >= limit {
+                break;
+            }
+            let checkpoint = self.checkpoint();
+            self.speculation_depth += 1;
+            self.set_cell(position, cell);
+            let result = match self.solve_lines() {
+                Ok(()) => self.collect_solutions(limit, solutions),
+                Err(SolverError::Contradiction(_)) => Ok(()),
+                Err(e) => Err(e),
+            };
+            self.speculation_depth -= 1;
+            self.undo_to(checkpoint);
+            self.release(checkpoint);
+            self.clear_line_queue();
+
+            match result {
+                Ok(()) => {}
+                Err(SolverError::Aborted(e)) => return Err(self.aborted_error(e.reason)),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Cells that are not the same in all `solutions`, in row order.
+pub fn differing_cells(solutions: &[Board<Cell>]) -> Vec<Vec2> {
+    let Some((first, rest)) = solutions.split_first() else {
+        return Vec::new();
+    };
+    let size = first.size();
+    (0..size.row)
+        .flat_map(|row| (0..size.column).map(move |column| Vec2::new(row, column)))
+        .filter(|&position| {
+            rest.iter()
+                .any(|board| board.value(position) != first.value(position))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::NullDisplay;
+    use crate::generator::line_hint;
+
+    fn solver(size: Vec2, rows: &[&[usize]], columns: &[&[usize]], level: SolveLevel) -> Solver {
+        let mut solver = Solver::new(
+            size,
+            rows.iter().map(|hint| hint.to_vec()).collect(),
+            columns.iter().map(|hint| hint.to_vec()).collect(),
+            Box::new(NullDisplay),
+        )
+        .unwrap();
+        solver.set_solve_level(level);
+        solver
+    }
+
+    // 라인 논리로는 막히지만 한 칸을 가정해 보면 모순이 나는 퍼즐
+    const ROWS: &[&[usize]] = &[&[1, 1, 1], &[1], &[1, 1], &[1], &[1, 1]];
+    const COLUMNS: &[&[usize]] = &[&[1], &[2], &[1, 1], &[1, 1], &[1, 1]];
+
+    #[test]
+    fn test_probe_solves_beyond_line_logic() {
+        let size = Vec2::new(5, 5);
+        let mut line = solver(size, ROWS, COLUMNS, SolveLevel::Line);
+        line.solve().unwrap();
+        assert!(!line.is_solved());
+
+        let mut probe = solver(size, ROWS, COLUMNS, SolveLevel::Probe);
+        probe.solve().unwrap();
+        assert!(probe.is_solved());
+        for (row, hint) in ROWS.iter().enumerate() {
+            assert_eq!(line_hint(probe.board.iter_row(row)), hint.to_vec());
+        }
+        for (column, hint) in COLUMNS.iter().enumerate() {
+            assert_eq!(line_hint(probe.board.iter_column(column)), hint.to_vec());
+        }
+    }
+
+    #[test]
+    fn test_search() {
+        // 해가 두 개라서 탐색만 풀 수 있다
+        let size = Vec2::new(2, 2);
+        let mut probe = solver(size, &[&[1], &[1]], &[&[1], &[1]], SolveLevel::Probe);
+        probe.solve().unwrap();
+        assert!(!probe.is_solved());
+
+        let mut search = solver(size, &[&[1], &[1]], &[&[1], &[1]], SolveLevel::Search);
+        search.solve().unwrap();
+        assert!(search.is_solved());
+
+        let mut impossible = solver(size, &[&[2], &[0]], &[&[0], &[1]], SolveLevel::Search);
+        assert!(matches!(
+            impossible.solve(),
+            Err(SolverError::Contradiction(_))
+        ));
+    }
+
+    #[test]
+    fn test_enumerate_solutions() {
+        let size = Vec2::new(2, 2);
+        let mut two = solver(size, &[&[1], &[1]], &[&[1], &[1]], SolveLevel::Line);
+        let solutions = two.enumerate_solutions(5).unwrap();
+        assert_eq!(solutions.len(), 2);
+        assert_eq!(differing_cells(&solutions).len(), 4);
+        assert!(!two.is_solved());
+        assert_eq!(two.enumerate_solutions(1).unwrap().len(), 1);
+
+        let mut unique = solver(Vec2::new(5, 5), ROWS, COLUMNS, SolveLevel::Line);
+        let solutions = unique.enumerate_solutions(5).unwrap();
+        assert_eq!(solutions.len(), 1);
+        assert!(differing_cells(&solutions).is_empty());
+
+        let mut impossible = solver(size, &[&[2], &[0]], &[&[0], &[1]], SolveLevel::Line);
+        assert!(impossible.enumerate_solutions(5).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,507 @@
+//! A small CDCL SAT solver: two watched literals, first-UIP clause learning, VSIDS variable
+//! order with phase saving, and Luby restarts. Learnt clauses are never deleted.
+
+use std::mem;
+
+/// Variable `index` as positive (`index * 2`) or negative (`index * 2 + 1`) literal.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) struct Lit(u32);
+
+impl Lit {
+    pub fn new(var: usize, negative: bool) -> Self {
+        Lit((var as u32) << 1 | negative as u32)
+    }
+
+    /// DIMACS literal: 1-based variable, negative when negated.
+    pub fn from_dimacs(literal: i32) -> Self {
+        Lit::new(literal.unsigned_abs() as usize - 1, literal < 0)
+    }
+
+    fn var(self) -> usize {
+        (self.0 >> 1) as usize
+    }
+
+    fn is_negative(self) -> bool {
+        self.0 & 1 == 1
+    }
+
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl std::ops::Not for Lit {
+    type Output = Lit;
+
+    fn not(self) -> Lit {
+        Lit(self.0 ^ 1)
+    }
+}
+
+/// Why [`CdclSolver::solve`] stopped without an answer.
+pub(crate) struct Interrupted;
+
+/// Max-heap of unassigned variables ordered by activity.
+struct VarOrder {
+    heap: Vec<usize>,
+    position: Vec<Option<usize>>,
+    activity: Vec<f64>,
+    increment: f64,
+}
+
+impl VarOrder {
+    fn new(variables: usize) -> Self {
+        Self {
+            heap: (0..variables).collect(),
+            position: (0..variables).map(Some).collect(),
+            activity: vec![0.0; variables],
+            increment: 1.0,
+        }
+    }
+
+    fn higher(&self, a: usize, b: usize) -> bool {
+        self.activity[self.heap[a]] > self.activity[self.heap[b]]
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.position[self.heap[a]] = Some(a);
+        self.position[self.heap[b]] = Some(b);
+    }
+
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if !self.higher(index, parent) {
+                break;
+            }
+            self.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let mut largest = index;
+            for child in [2 * index + 1, 2 * index + 2] {
+                if child < self.heap.len() && self.higher(child, largest) {
+                    largest = child;
+                }
+            }
+            if largest == index {
+                break;
+            }
+            self.swap(index, largest);
+            index = largest;
+        }
+    }
+
+    fn insert(&mut self, var: usize) {
+        if self.position[var].is_some() {
+            return;
+        }
+        self.heap.push(var);
+        let index = self.heap.len() - 1;
+        self.position[var] = Some(index);
+        self.sift_up(index);
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let last = self.heap.len() - 1;
+        self.swap(0, last);
+        let var = self.heap.pop()?;
+        self.position[var] = None;
+        if !self.heap.is_empty() {
+            self.sift_down(0);
+        }
+        Some(var)
+    }
+
+    fn bump(&mut self, var: usize) {
+        self.activity[var] += self.increment;
+        if self.activity[var] > 1e100 {
+            // 순서는 그대로 두고 크기만 줄인다
+            self.activity
+                .iter_mut()
+                .for_each(|activity| *activity *= 1e-100);
+            self.increment *= 1e-100;
+        }
+        if let Some(index) = self.position[var] {
+            self.sift_up(index);
+        }
+    }
+
+    fn decay(&mut self) {
+        self.increment /= 0.95;
+    }
+}
+
+pub(crate) struct CdclSolver {
+    clauses: Vec<Vec<Lit>>,
+    /// Clauses watching each literal, checked when the literal becomes false.
+    watches: Vec<Vec<usize>>,
+    values: Vec<Option<bool>>,
+    levels: Vec<usize>,
+    reasons: Vec<Option<usize>>,
+    trail: Vec<Lit>,
+    trail_limits: Vec<usize>,
+    propagated: usize,
+    order: VarOrder,
+    phases: Vec<bool>,
+    seen: Vec<bool>,
+    /// An empty clause was added.
+    unsatisfiable: bool,
+}
+
+impl CdclSolver {
+    pub fn new(variables: usize) -> Self {
+        Self {
+            clauses: Vec::new(),
+            watches: vec![Vec::new(); variables * 2],
+            values: vec![None; variables],
+            levels: vec![0; variables],
+            reasons: vec![None; variables],
+            trail: Vec::new(),
+            trail_limits: Vec::new(),
+            propagated: 0,
+            order: VarOrder::new(variables),
+            phases: vec![false; variables],
+            seen: vec![false; variables],
+            unsatisfiable: false,
+        }
+    }
+
+    fn value(&self, lit: Lit) -> Option<bool> {
+        self.values[lit.var()].map(|value| value != lit.is_negative())
+    }
+
+    fn level(&self) -> usize {
+        self.trail_limits.len()
+    }
+
+    /// Adds a clause before solving.
+    pub fn add_clause(&mut self, mut clause: Vec<Lit>) {
+        clause.sort_unstable_by_key(|lit| lit.0);
+        clause.dedup();
+        if clause.windows(2).any(|pair| pair[0] == !pair[1]) {
+            return;
+        }
+        match clause.len() {
+            0 => self.unsatisfiable = true,
+            1 => match self.value(clause[0]) {
+                Some(true) => {}
+                Some(false) => self.unsatisfiable = true,
+                None => self.assign(clause[0], None),
+            },
+            _ => {
+                self.watch(clause);
+            }
+        }
+    }
+
+    fn watch(&mut self, clause: Vec<Lit>) -> usize {
+        let index = self.clauses.len();
+        self.watches[clause[0].index()].push(index);
+        self.watches[clause[1].index()].push(index);
+        self.clauses.push(clause);
+        index
+    }
+
+    fn assign(&mut self, lit: Lit, reason: Option<usize>) {
+        let var = lit.var();
+        self.values[var] = Some(!lit.is_negative());
+        self.levels[var] = self.level();
+        self.reasons[var] = reason;
+        self.trail.push(lit);
+    }
+
+    /// Returns a conflicting clause, if any.
+    fn propagate(&mut self) -> Option<usize> {
+        while self.propagated < self.trail.len() {
+            let false_lit = !self.trail[self.propagated];
+            self.propagated += 1;
+
+            let mut watchers = mem::take(&mut self.watches[false_lit.index()]);
+            let mut kept = 0;
+            let mut conflict = None;
+            let mut index = 0;
+            while index < watchers.len() {
+                let clause_index = watchers[index];
+                index += 1;
+
+                let clause = &mut self.clauses[clause_index];
+                if clause[0] == false_lit {
+                    clause.swap(0, 1);
+                }
+                let first = clause[0];
+                let values = &self.values;
+                let value = |lit: Lit| values[lit.var()].map(|value| value != lit.is_negative());
+                if value(first) == Some(true) {
+                    watchers[kept] = clause_index;
+                    kept += 1;
+                    continue;
+                }
+
+                if let Some(k) = (2..clause.len()).find(|&k| value(clause[k]) != Some(false)) {
+                    clause.swap(1, k);
+                    let new_watch = clause[1];
+                    self.watches[new_watch.index()].push(clause_index);
+                    continue;
+                }
+
+                watchers[kept] = clause_index;
+                kept += 1;
+                if value(first) == Some(false) {
+                    conflict = Some(clause_index);
+                    while index < watchers.len() {
+                        watchers[kept] = watchers[index];
+                        kept += 1;
+                        index += 1;
+                    }
+                } else {
+                    self.assign(first, Some(clause_index));
+                }
+            }
+            watchers.truncate(kept);
+            self.watches[false_lit.index()] = watchers;
+
+            if conflict.is_some() {
+                return conflict;
+            }
+        }
+        None
+    }
+
+    /// First-UIP learning. Returns the learnt clause, asserting literal first, and the level to
+    /// go back to.
+    fn analyze(&mut self, conflict: usize) -> (Vec<Lit>, usize) {
+        let mut learnt = vec![Lit(0)];
+        let mut pending = 0;
+        let mut implied: Option<Lit> = None;
+        let mut clause_index = conflict;
+        let mut trail_index = self.trail.len();
+
+        loop {
+            let skip = usize::from(implied.is_some());
+            for k in skip..self.clauses[clause_index].len() {
+                let lit = self.clauses[clause_index][k];
+                let var = lit.var();
+                if self.seen[var] || self.levels[var] == 0 {
+                    continue;
+                }
+                self.seen[var] = true;
+                self.order.bump(var);
+                if self.levels[var] >= self.level() {
+                    pending += 1;
+                } else {
+                    learnt.push(lit);
+                }
+            }
+
+            let lit = loop {
+                trail_index -= 1;
+                let lit = self.trail[trail_index];
+                if self.seen[lit.var()] {
+                    break lit;
+                }
+            };
+            self.seen[lit.var()] = false;
+            implied = Some(lit);
+            pending -= 1;
+            if pending == 0 {
+                break;
+            }
+            clause_index = self.reasons[lit.var()].expect("implied literal has a reason");
+        }
+        learnt[0] = !implied.expect("conflict has a literal at the current level");
+
+        for lit in &learnt[1..] {
+            self.seen[lit.var()] = false;
+        }
+
+        let mut back_level = 0;
+        if learnt.len() > 1 {
+            let (max_index, level) = learnt[1..]
+                .iter()
+                .enumerate()
+                .map(|(k, lit)| (k + 1, self.levels[lit.var()]))
+                .max_by_key(|&(_, level)| level)
+                .unwrap_or((1, 0));
+            learnt.swap(1, max_index);
+            back_level = level;
+        }
+        (learnt, back_level)
+    }
+
+    fn backtrack(&mut self, level: usize) {
+        if self.level() <= level {
+            return;
+        }
+        let limit = self.trail_limits[level];
+        for lit in self.trail.drain(limit..) {
+            let var = lit.var();
+            self.phases[var] = !lit.is_negative();
+            self.values[var] = None;
+            self.reasons[var] = None;
+            self.order.insert(var);
+        }
+        self.trail_limits.truncate(level);
+        self.propagated = limit;
+    }
+
+    fn decide(&mut self) -> Option<Lit> {
+        while let Some(var) = self.order.pop() {
+            if self.values[var].is_none() {
+                return Some(Lit::new(var, !self.phases[var]));
+            }
+        }
+        None
+    }
+
+    /// Finds a model, `None` if there is none. `check` is called every few conflicts and can
+    /// interrupt the search.
+    pub fn solve(
+        &mut self,
+        mut check: impl FnMut() -> Result<(), Interrupted>,
+    ) -> Result<Option<Vec<bool>>, Interrupted> {
+        if self.unsatisfiable || self.propagate().is_some() {
+            return Ok(None);
+        }
+
+        let mut conflicts = 0usize;
+        let mut restart = 1;
+        let mut restart_limit = luby(restart) * 100;
+        loop {
+            if let Some(conflict) = self.propagate() {
+                if self.level() == 0 {
+                    return Ok(None);
+                }
+                conflicts += 1;
+                if conflicts.is_multiple_of(256) {
+                    check()?;
+                }
+
+                let (learnt, back_level) = self.analyze(conflict);
+                self.backtrack(back_level);
+                if learnt.len() == 1 {
+                    self.assign(learnt[0], None);
+                } else {
+                    let asserting = learnt[0];
+                    let index = self.watch(learnt);
+                    self.assign(asserting, Some(index));
+                }
+                self.order.decay();
+
+                if conflicts >= restart_limit {
+                    restart += 1;
+                    restart_limit = conflicts + luby(restart) * 100;
+                    self.backtrack(0);
+                }
+                continue;
+            }
+
+            match self.decide() {
+                Some(lit) => {
+                    self.trail_limits.push(self.trail.len());
+                    self.assign(lit, None);
+                }
+                None => {
+                    return Ok(Some(
+                        self.values
+                            .iter()
+                            .map(|value| value == &Some(true))
+                            .collect(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Values fixed without any decision, `None` for the others.
+    pub fn fixed_values(&self) -> Vec<Option<bool>> {
+        self.values
+            .iter()
+            .zip(&self.levels)
+            .map(|(&value, &level)| value.filter(|_| level == 0))
+            .collect()
+    }
+}
+
+/// The `i`-th element (from 1) of the Luby sequence 1, 1, 2, 1, 1, 2, 4, ...
+fn luby(i: usize) -> usize {
+    let mut x = i - 1;
+    let (mut size, mut exponent) = (1, 0);
+    while size < x + 1 {
+        exponent += 1;
+        size = 2 * size + 1;
+    }
+    while size - 1 != x {
+        size = (size - 1) >> 1;
+        exponent -= 1;
+        x %= size;
+    }
+    1 << exponent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(variables: usize, clauses: &[&[i32]]) -> Option<Vec<bool>> {
+        let mut solver = CdclSolver::new(variables);
+        for clause in clauses {
+            solver.add_clause(clause.iter().map(|&lit| Lit::from_dimacs(lit)).collect());
+        }
+        solver.solve(|| Ok(())).ok().flatten()
+    }
+
+    fn satisfies(model: &[bool], clauses: &[&[i32]]) -> bool {
+        clauses.iter().all(|clause| {
+            clause
+                .iter()
+                .any(|&lit| model[lit.unsigned_abs() as usize - 1] == (lit > 0))
+        })
+    }
+
+    #[test]
+    fn test_luby() {
+        let sequence = (1..=15).map(luby).collect::<Vec<_>>();
+        assert_eq!(sequence, vec![1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8]);
+    }
+
+    #[test]
+    fn test_satisfiable() {
+        let clauses: &[&[i32]] = &[&[1, 2], &[-1, 3], &[-2, 3], &[-3, 4], &[-4, -1]];
+        let model = solve(4, clauses).unwrap();
+        assert!(satisfies(&model, clauses));
+    }
+
+    #[test]
+    fn test_unsatisfiable() {
+        assert_eq!(solve(1, &[&[1], &[-1]]), None);
+        assert_eq!(solve(2, &[&[1, 2], &[1, -2], &[-1, 2], &[-1, -2]]), None);
+    }
+
+    #[test]
+    fn test_pigeonhole() {
+        // 비둘기 5마리를 구멍 4개에 넣을 수 없다
+        let (pigeons, holes) = (5, 4);
+        let var = |pigeon: i32, hole: i32| pigeon * holes + hole + 1;
+        let mut clauses = Vec::new();
+        for pigeon in 0..pigeons {
+            clauses.push((0..holes).map(|hole| var(pigeon, hole)).collect::<Vec<_>>());
+        }
+        for hole in 0..holes {
+            for a in 0..pigeons {
+                for b in a + 1..pigeons {
+                    clauses.push(vec![-var(a, hole), -var(b, hole)]);
+                }
+            }
+        }
+        let clauses = clauses.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        assert_eq!(solve((pigeons * holes) as usize, &clauses), None);
+    }
+}
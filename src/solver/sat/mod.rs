@@ -0,0 +1,362 @@
+mod cdcl;
+
+use std::io::{self, Write};
+use std::time::Instant;
+
+use cdcl::{CdclSolver, Interrupted, Lit};
+
+use super::error::{AbortedError, SolverError};
+use super::limits::{AbortReason, CancellationToken, SolveLimits};
+use super::types::LineDirection;
+use super::{Cell, Solver};
+use crate::board::{Board, Vec2};
+
+/// A formula in conjunctive normal form. Literals use DIMACS numbering: variable `v` is `v` when
+/// true and `-v` when false, starting from 1.
+#[derive(Clone, Default, Debug)]
+pub struct Cnf {
+    pub variables: usize,
+    pub clauses: Vec<Vec<i32>>,
+}
+
+impl Cnf {
+    fn new_variable(&mut self) -> i32 {
+        self.variables += 1;
+        self.variables as i32
+    }
+
+    fn add(&mut self, clause: Vec<i32>) {
+        self.clauses.push(clause);
+    }
+
+    pub fn write_dimacs(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "p cnf {} {}", self.variables, self.clauses.len())?;
+        for clause in &self.clauses {
+            for literal in clause {
+                write!(out, "{} ", literal)?;
+            }
+            writeln!(out, "0")?;
+        }
+        Ok(())
+    }
+}
+
+/// Solves a puzzle completely by encoding it as SAT, for puzzles that line logic cannot finish.
+///
+/// Cell `(row, column)` is variable `row * columns + column + 1`: true for filled.
+pub struct SatSolver {
+    size: Vec2,
+    row_hints: Vec<Vec<usize>>,
+    column_hints: Vec<Vec<usize>>,
+    limits: SolveLimits,
+    cancellation_token: CancellationToken,
+}
+
+impl SatSolver {
+    /// Validates the hints like [`Solver::new`].
+    pub fn new(
+        size: Vec2,
+        row_hints: Vec<Vec<usize>>,
+        column_hints: Vec<Vec<usize>>,
+    ) -> Result<Self, SolverError> {
+        if size.row == 0 || size.column == 0 || size.row.checked_mul(size.column).is_none() {
+            return Err(SolverError::InvalidBoardSize(size.column, size.row));
+        }
+        Solver::validate_hint_count(size.row, &row_hints, LineDirection::Row)?;
+        Solver::validate_hint_count(size.column, &column_hints, LineDirection::Column)?;
+        Solver::validate_hints(size.column, &row_hints, LineDirection::Row)?;
+        Solver::validate_hints(size.row, &column_hints, LineDirection::Column)?;
+
+        Ok(Self {
+            size,
+            row_hints,
+            column_hints,
+            limits: SolveLimits::default(),
+            cancellation_token: CancellationToken::default(),
+        })
+    }
+
+    /// Only the deadline of `limits` applies.
+    pub fn set_limits(&mut self, limits: SolveLimits) {
+        self.limits = limits;
+    }
+
+    pub fn set_cancellation_token(&mut self, token: CancellationToken) {
+        self.cancellation_token = token;
+    }
+
+    pub fn to_cnf(&self) -> Cnf {
+        let mut cnf = Cnf {
+            variables: self.size.row * self.size.column,
+            clauses: Vec::new(),
+        };
+        let cell = |row: usize, column: usize| (row * self.size.column + column + 1) as i32;
+
+        for (row, hint) in self.row_hints.iter().enumerate() {
+            let cells = (0..self.size.column)
+                .map(|column| cell(row, column))
+                .collect::<Vec<_>>();
+            encode_line(&mut cnf, &cells, hint);
+        }
+        for (column, hint) in self.column_hints.iter().enumerate() {
+            let cells = (0..self.size.row)
+                .map(|row| cell(row, column))
+                .collect::<Vec<_>>();
+            encode_line(&mut cnf, &cells, hint);
+        }
+        cnf
+    }
+
+    /// Returns a solution, or `None` if the hints have none. If the puzzle has several
+    /// solutions, any one of them is returned.
+    pub fn solve(&self) -> Result<Option<Board<Cell>>, SolverError> {
+        let cnf = self.to_cnf();
+        let mut solver = CdclSolver::new(cnf.variables);
+        for clause in &cnf.clauses {
+            solver.add_clause(clause.iter().map(|&lit| Lit::from_dimacs(lit)).collect());
+        }
+
+        let mut reason = AbortReason::Cancelled;
+        let result = solver.solve(|| {
+            if self.cancellation_token.is_cancelled() {
+                return Err(Interrupted);
+            }
+            if self
+                .limits
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+            {
+                reason = AbortReason::Timeout;
+                return Err(Interrupted);
+            }
+            Ok(())
+        });
+
+        match result {
+            Ok(model) => Ok(model.map(|model| self.to_board(|index| Some(model[index])))),
+            Err(Interrupted) => {
+                // 결정 없이 정해진 칸만 알려 준다
+                let fixed = solver.fixed_values();
+                Err(SolverError::Aborted(AbortedError {
+                    reason,
+                    board: self.to_board(|index| fixed[index]),
+                    checkpoint_error: None,
+                }))
+            }
+        }
+    }
+
+    fn to_board(&self, value: impl Fn(usize) -> Option<bool>) -> Board<Cell> {
+        let mut board = Board::new(self.size, Cell::Unknown);
+        for row in 0..self.size.row {
+            for column in 0..self.size.column {
+                *board.value_mut(Vec2::new(row, column)) =
+                    match value(row * self.size.column + column) {
+                        Some(true) => Cell::Block,
+                        Some(false) => Cell::Blank,
+                        None => Cell::Unknown,
+                    };
+            }
+        }
+        board
+    }
+}
+
+/// Encodes that `cells` read `hint`.
+///
+/// Each block gets a variable per possible start, and a second "started at or before"
+/// variable per start that keeps the blocks in order with binary clauses.
+fn encode_line(cnf: &mut Cnf, cells: &[i32], hint: &[usize]) {
+    let blocks = hint
+        .iter()
+        .copied()
+        .filter(|&block| block > 0)
+        .collect::<Vec<_>>();
+    let length = cells.len();
+
+    if blocks.is_empty() {
+        for &cell in cells {
+            cnf.add(vec![-cell]);
+        }
+        return;
+    }
+
+    // 블록마다 가장 앞의 시작 위치와, 거기서 뒤로 밀 수 있는 칸 수
+    let total = blocks.iter().sum::<usize>() + blocks.len() - 1;
+    let mut earliest = Vec::with_capacity(blocks.len());
+    let mut position = 0;
+    for &block in &blocks {
+        earliest.push(position);
+        position += block + 1;
+    }
+    let slack = length - total;
+
+    let mut starts = Vec::with_capacity(blocks.len());
+    let mut started = Vec::with_capacity(blocks.len());
+    for (k, &block) in blocks.iter().enumerate() {
+        let start = (0..=slack).map(|_| cnf.new_variable()).collect::<Vec<_>>();
+        let before = (0..=slack).map(|_| cnf.new_variable()).collect::<Vec<_>>();
+
+        // 정확히 한 곳에서 시작한다
+        cnf.add(start.clone());
+        for offset in 0..=slack {
+            cnf.add(vec![-start[offset], before[offset]]);
+            if offset > 0 {
+                cnf.add(vec![-before[offset - 1], before[offset]]);
+                cnf.add(vec![-before[offset - 1], -start[offset]]);
+                cnf.add(vec![-before[offset], before[offset - 1], start[offset]]);
+            } else {
+                cnf.add(vec![-before[offset], start[offset]]);
+            }
+
+            let first = earliest[k] + offset;
+            for &cell in &cells[first..first + block] {
+                cnf.add(vec![-start[offset], cell]);
+            }
+            if first > 0 {
+                cnf.add(vec![-start[offset], -cells[first - 1]]);
+            }
+            if first + block < length {
+                cnf.add(vec![-start[offset], -cells[first + block]]);
+            }
+        }
+        starts.push(start);
+        started.push(before);
+    }
+
+    // 다음 블록은 앞 블록이 끝나고 한 칸 뒤부터 시작한다. 두 블록의 earliest 차이가
+    // 정확히 block + 1이므로, 다음 블록의 offset이 앞 블록의 offset보다 작지 않으면 된다.
+    for k in 0..blocks.len() - 1 {
+        for offset in 0..=slack {
+            cnf.add(vec![-starts[k + 1][offset], started[k][offset]]);
+            if offset > 0 {
+                cnf.add(vec![-starts[k][offset], -started[k + 1][offset - 1]]);
+            }
+        }
+    }
+
+    // 칠해진 칸은 어떤 블록에 덮여 있다
+    for (index, &cell) in cells.iter().enumerate() {
+        let mut clause = vec![-cell];
+        for ((&block, &earliest), start) in blocks.iter().zip(&earliest).zip(&starts) {
+            for (offset, &variable) in start.iter().enumerate() {
+                let first = earliest + offset;
+                if first <= index && index < first + block {
+                    clause.push(variable);
+                }
+            }
+        }
+        cnf.add(clause);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::NullDisplay;
+    use crate::generator::{derive_hints, line_hint};
+    use crate::solver::parser::{FileSolverParser, HtmlTableSolverParser, SolverParser};
+
+    fn sat_solver(parser: &dyn SolverParser) -> SatSolver {
+        let puzzle = parser.parse().unwrap();
+        SatSolver::new(puzzle.board_size, puzzle.row_hints, puzzle.column_hints).unwrap()
+    }
+
+    fn matches_hints(
+        board: &Board<Cell>,
+        row_hints: &[Vec<usize>],
+        column_hints: &[Vec<usize>],
+    ) -> bool {
+        let size = board.size();
+        let hint = |hint: &[usize]| {
+            hint.iter()
+                .copied()
+                .filter(|&number| number > 0)
+                .collect::<Vec<_>>()
+        };
+        (0..size.row).all(|row| {
+            line_hint(&board.iter_row(row).copied().collect::<Vec<_>>()) == hint(&row_hints[row])
+        }) && (0..size.column).all(|column| {
+            line_hint(&board.iter_column(column).copied().collect::<Vec<_>>())
+                == hint(&column_hints[column])
+        })
+    }
+
+    #[test]
+    fn test_matches_line_solver() {
+        let html = include_str!("../../../sample/table/data2.txt");
+        let parsers: [Box<dyn SolverParser>; 2] = [
+            Box::new(FileSolverParser::new("./sample/data1.txt")),
+            Box::new(HtmlTableSolverParser::new(html)),
+        ];
+        for parser in parsers {
+            let mut solver = parser.create_solver(Box::new(NullDisplay)).unwrap();
+            solver.solve().unwrap();
+            assert!(solver.is_solved());
+
+            let board = sat_solver(parser.as_ref()).solve().unwrap().unwrap();
+            assert!(board.iter_all().eq(solver.board().iter_all()));
+        }
+    }
+
+    #[test]
+    fn test_beyond_line_logic() {
+        // 라인 논리로는 한 칸도 정할 수 없지만 해는 있다
+        let row_hints = vec![vec![1], vec![1]];
+        let column_hints = vec![vec![1], vec![1]];
+        let sat = SatSolver::new(Vec2::new(2, 2), row_hints.clone(), column_hints.clone()).unwrap();
+        let board = sat.solve().unwrap().unwrap();
+        assert!(matches_hints(&board, &row_hints, &column_hints));
+    }
+
+    #[test]
+    fn test_unsatisfiable() {
+        let sat = SatSolver::new(
+            Vec2::new(2, 2),
+            vec![vec![2], vec![0]],
+            vec![vec![0], vec![1]],
+        )
+        .unwrap();
+        assert!(sat.solve().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_random_boards() {
+        let mut seed = 12345u64;
+        for _ in 0..30 {
+            let mut board = Board::new(Vec2::new(7, 9), Cell::Blank);
+            for index in 0..63 {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                if seed >> 62 != 0 {
+                    *board.value_mut(Vec2::new(index / 9, index % 9)) = Cell::Block;
+                }
+            }
+            let hints = derive_hints(&board);
+            let sat = SatSolver::new(
+                board.size(),
+                hints.row_hints.clone(),
+                hints.column_hints.clone(),
+            )
+            .unwrap();
+            let solved = sat.solve().unwrap().unwrap();
+            assert!(matches_hints(
+                &solved,
+                &hints.row_hints,
+                &hints.column_hints
+            ));
+        }
+    }
+
+    #[test]
+    fn test_dimacs() {
+        let sat = SatSolver::new(Vec2::new(1, 2), vec![vec![0]], vec![vec![0], vec![0]]).unwrap();
+        let mut out = Vec::new();
+        sat.to_cnf().write_dimacs(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "p cnf 2 4\n-1 0\n-2 0\n-1 0\n-2 0\n"
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt::Display;
+
+use super::types::Line;
+
+/// Decides which queued line is solved next.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum LineHeuristic {
+    /// The line with the fewest remaining placements, which is the cheapest to solve.
+    #[default]
+    FewestPossibilities,
+    MostUnknownCells,
+    /// The line whose cells changed last, like a stack.
+    MostRecentlyChanged,
+    /// Lines in the order they were queued.
+    RoundRobin,
+    /// The line with the most changed cells per remaining placement.
+    InformationGain,
+}
+
+impl LineHeuristic {
+    pub const ALL: [LineHeuristic; 5] = [
+        LineHeuristic::FewestPossibilities,
+        LineHeuristic::MostUnknownCells,
+        LineHeuristic::MostRecentlyChanged,
+        LineHeuristic::RoundRobin,
+        LineHeuristic::InformationGain,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineHeuristic::FewestPossibilities => "fewest-possibilities",
+            LineHeuristic::MostUnknownCells => "most-unknown-cells",
+            LineHeuristic::MostRecentlyChanged => "most-recently-changed",
+            LineHeuristic::RoundRobin => "round-robin",
+            LineHeuristic::InformationGain => "information-gain",
+        }
+    }
+}
+
+impl Display for LineHeuristic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// What a heuristic needs to know about a line to rank it.
+pub(crate) struct LineStats {
+    pub possibilities: usize,
+    pub unknown_cells: usize,
+    /// Cells of the line that changed since it was last solved.
+    pub changed_cells: usize,
+}
+
+impl LineHeuristic {
+    /// Lower is solved sooner. `tick` grows every time a line is queued.
+    pub(crate) fn cost(&self, stats: &LineStats, tick: u64) -> u64 {
+        match self {
+            LineHeuristic::FewestPossibilities => stats.possibilities as u64,
+            LineHeuristic::MostUnknownCells => u64::MAX - stats.unknown_cells as u64,
+            LineHeuristic::MostRecentlyChanged => u64::MAX - tick,
+            LineHeuristic::RoundRobin => tick,
+            LineHeuristic::InformationGain => {
+                (stats.possibilities as u64).saturating_mul(1024) / (stats.changed_cells as u64 + 1)
+            }
+        }
+    }
+
+    /// Whether queueing an already queued line moves it to a new position.
+    pub(crate) fn requeues(&self) -> bool {
+        !matches!(self, LineHeuristic::RoundRobin)
+    }
+}
+
+/// Priority queue of lines, indexed like `Solver::possibilities`.
+///
+/// Changing the cost of a queued line pushes a new entry; the old one is skipped when popped.
+pub(crate) struct LineQueue {
+    heap: BinaryHeap<Reverse<(u64, u64, usize)>>,
+    lines: Vec<Line>,
+    /// Stamp of the live entry of each line, or `None` if the line is not queued.
+    stamps: Vec<Option<u64>>,
+    next_stamp: u64,
+    len: usize,
+}
+
+impl LineQueue {
+    pub fn new(lines: Vec<Line>) -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            stamps: vec![None; lines.len()],
+            lines,
+            next_stamp: 0,
+            len: 0,
+        }
+    }
+
+    pub fn tick(&self) -> u64 {
+        self.next_stamp
+    }
+
+    /// Moves the tick forward to at least `tick`.
+    pub fn advance_tick(&mut self, tick: u64) {
+        self.next_stamp = self.next_stamp.max(tick);
+    }
+
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.stamps.iter_mut().for_each(|stamp| *stamp = None);
+        self.len = 0;
+    }
+
+    #[cfg_attr(not(feature = "parallel"), allow(dead_code))]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        self.stamps[index].is_some()
+    }
+
+    pub fn push(&mut self, index: usize, cost: u64) {
+        if self.stamps[index].is_none() {
+            self.len += 1;
+        }
+        let stamp = self.next_stamp;
+        self.next_stamp += 1;
+        self.stamps[index] = Some(stamp);
+        self.heap.push(Reverse((cost, stamp, index)));
+
+        // 오래된 항목이 너무 쌓이면 정리한다
+        if self.heap.len() > 4 * self.lines.len() + 16 {
+            let stamps = &self.stamps;
+            self.heap
+                .retain(|Reverse((_, stamp, index))| stamps[*index] == Some(*stamp));
+        }
+    }
+
+    pub fn remove(&mut self, index: usize) -> bool {
+        if self.stamps[index].take().is_some() {
+            self.len -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_stale(&mut self) {
+        while let Some(Reverse((_, stamp, index))) = self.heap.peek() {
+            if self.stamps[*index] == Some(*stamp) {
+                break;
+            }
+            self.heap.pop();
+        }
+    }
+
+    #[cfg_attr(not(feature = "parallel"), allow(dead_code))]
+    pub fn peek(&mut self) -> Option<Line> {
+        self.skip_stale();
+        self.heap
+            .peek()
+            .map(|Reverse((_, _, index))| self.lines[*index])
+    }
+
+    pub fn pop(&mut self) -> Option<(usize, Line)> {
+        self.skip_stale();
+        let Reverse((_, _, index)) = self.heap.pop()?;
+        self.remove(index);
+        Some((index, self.lines[index]))
+    }
+
+    /// Queued line indices and their costs, in the order they would be popped.
+    pub fn entries(&self) -> Vec<(u64, usize)> {
+        let mut entries = self
+            .heap
+            .iter()
+            .filter(|Reverse((_, stamp, index))| self.stamps[*index] == Some(*stamp))
+            .map(|Reverse(entry)| *entry)
+            .collect::<Vec<_>>();
+        entries.sort_unstable();
+        entries
+            .into_iter()
+            .map(|(cost, _, index)| (cost, index))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// The first `count` queued lines in the order they would be popped, without sorting the
+    /// rest of the queue.
+    pub fn first(&self, count: usize) -> Vec<Line> {
+        let mut entries = self
+            .heap
+            .iter()
+            .filter(|Reverse((_, stamp, index))| self.stamps[*index] == Some(*stamp))
+            .map(|Reverse(entry)| *entry)
+            .collect::<Vec<_>>();
+        if entries.len() > count {
+            if count == 0 {
+                return Vec::new();
+            }
+            entries.select_nth_unstable(count - 1);
+            entries.truncate(count);
+        }
+        entries.sort_unstable();
+        entries
+            .into_iter()
+            .map(|(_, _, index)| self.lines[index])
+            .collect()
+    }
+
+    /// Queued lines in the order they would be popped.
+    #[cfg_attr(not(feature = "parallel"), allow(dead_code))]
+    pub fn ordered(&self) -> Vec<Line> {
+        self.entries()
+            .into_iter()
+            .map(|(_, index)| self.lines[index])
+            .collect()
+    }
+
+    /// Queued line indices, in no particular order.
+    pub fn queued(&self) -> impl Iterator<Item = usize> + '_ {
+        self.stamps
+            .iter()
+            .enumerate()
+            .filter_map(|(index, stamp)| stamp.map(|_| index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::types::LineDirection;
+
+    fn queue() -> LineQueue {
+        LineQueue::new(
+            (0..4)
+                .map(|index| Line::new(LineDirection::Row, index))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_pop_by_cost() {
+        let mut queue = queue();
+        queue.push(0, 5);
+        queue.push(1, 2);
+        queue.push(2, 9);
+        // 비용을 바꾸면 예전 항목은 무시된다
+        queue.push(2, 1);
+        assert_eq!(queue.len, 3);
+        assert_eq!(
+            queue.ordered(),
+            [2, 1, 0].map(|index| Line::new(LineDirection::Row, index))
+        );
+
+        assert_eq!(queue.first(2), queue.ordered()[..2]);
+        assert_eq!(queue.first(5), queue.ordered());
+
+        assert_eq!(queue.pop().map(|(index, _)| index), Some(2));
+        assert_eq!(queue.pop().map(|(index, _)| index), Some(1));
+        assert_eq!(queue.pop().map(|(index, _)| index), Some(0));
+        assert_eq!(queue.pop(), None);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut queue = queue();
+        queue.push(0, 1);
+        queue.push(1, 2);
+        assert!(queue.remove(0));
+        assert!(!queue.remove(0));
+        assert!(!queue.contains(0));
+        assert_eq!(queue.peek(), Some(Line::new(LineDirection::Row, 1)));
+        assert_eq!(queue.len, 1);
+    }
+
+    #[test]
+    fn test_stale_entries_are_compacted() {
+        let mut queue = queue();
+        for cost in 0..1000 {
+            queue.push(cost as usize % 4, cost);
+        }
+        assert!(queue.heap.len() <= 4 * 4 + 16);
+        assert_eq!(queue.len, 4);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::board::Board;
 
-use super::{cell::Cell, types::Line};
+use super::{cell::Cell, heatmap::CellHeat, limits::AbortReason, types::Line};
 
 #[derive(Clone)]
 pub enum SolverState {
@@ -8,16 +8,41 @@ pub enum SolverState {
     Idle,
     Solving(SolvingContext),
     Solved,
+    Aborted(AbortReason),
 }
 
+/// Most queued lines sent with a solving step.
+pub const WAITING_LINES: usize = 10;
+
 #[derive(Clone)]
 pub struct SolvingContext {
     pub board: Board<Cell>,
     pub line: Line,
+    /// The first [`WAITING_LINES`] queued lines, in the order they will be solved. Empty unless
+    /// the display asks for it with [`SolverDisplay::wants_waiting_lines`].
     pub line_waiting: Vec<Line>,
+    /// Number of queued lines, including those left out of `line_waiting`.
+    pub queue_length: usize,
+    /// Only filled for displays that ask for it with [`SolverDisplay::wants_heatmap`].
+    pub heatmap: Option<Board<CellHeat>>,
 }
 
 pub trait SolverDisplay {
     fn change_state(&mut self, state: SolverState);
     fn update_progress(&mut self, progress: (usize, usize));
+
+    /// Called once with the validated hints, before the solver becomes idle.
+    fn set_hints(&mut self, _row_hints: &[Vec<usize>], _column_hints: &[Vec<usize>]) {}
+
+    /// Whether solving steps should come with a heatmap, which costs a pass over the surviving
+    /// placements of every changed line.
+    fn wants_heatmap(&self) -> bool {
+        false
+    }
+
+    /// Whether solving steps should list the next queued lines, which costs a pass over the
+    /// queue.
+    fn wants_waiting_lines(&self) -> bool {
+        false
+    }
 }
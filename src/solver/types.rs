@@ -1,6 +1,7 @@
 use std::fmt::{Debug, Display};
 
-use super::{cell::Cell, Solver};
+use super::{bit_line::BitLine, Solver};
+use crate::board::Vec2;
 
 #[derive(Clone, Copy, Hash, Eq, PartialEq, Debug)]
 pub enum LineDirection {
@@ -85,34 +86,28 @@ impl LineSolvingInfoProvider for Solver {
 }
 
 pub(super) trait LineProcessor {
-    fn get_line_cells(&self, line: Line) -> Vec<Cell>;
-    fn update_line(&mut self, line: Line, new_cells: &[Cell]);
+    fn get_line_cells(&self, line: Line) -> &BitLine;
+    fn update_line(&mut self, line: Line, new_cells: &BitLine);
 }
 
 impl LineProcessor for Solver {
-    fn get_line_cells(&self, line: Line) -> Vec<Cell> {
-        match line.direction() {
-            LineDirection::Row => self.board.iter_row(line.index()).cloned().collect(),
-            LineDirection::Column => self.board.iter_column(line.index()).cloned().collect(),
-        }
+    fn get_line_cells(&self, line: Line) -> &BitLine {
+        &self.line_bits[self.line_to_index(line)]
     }
 
-    fn update_line(&mut self, line: Line, new_cells: &[Cell]) {
-        let iter_mut: Box<dyn Iterator<Item = &mut Cell>> = match line.direction() {
-            LineDirection::Row => Box::new(self.board.iter_row_mut(line.index())),
-            LineDirection::Column => Box::new(self.board.iter_column_mut(line.index())),
-        };
+    fn update_line(&mut self, line: Line, new_cells: &BitLine) {
+        let changed = self
+            .get_line_cells(line)
+            .newly_known(new_cells)
+            .collect::<Vec<_>>();
 
-        iter_mut
-            .zip(new_cells.iter())
-            .enumerate()
-            .filter(|(_, (board_cell, &new_cell))| {
-                new_cell != Cell::Crash && new_cell != Cell::Unknown && **board_cell != new_cell
-            })
-            .for_each(|(index, (board_cell, &new_cell))| {
-                self.line_changed
-                    .insert(Line::new(line.direction().opposite(), index));
-                *board_cell = new_cell;
-            });
+        for (index, cell) in changed {
+            let position = match line.direction() {
+                LineDirection::Row => Vec2::new(line.index(), index),
+                LineDirection::Column => Vec2::new(index, line.index()),
+            };
+            self.change_cell(position, cell, Some(line));
+            self.mark_line_changed(Line::new(line.direction().opposite(), index));
+        }
     }
 }